@@ -23,13 +23,27 @@ impl axum::extract::FromRequestParts<AppState> for Values {
 
 #[html_form_actions::actions(axum, state = AppState)]
 mod index_page {
-    use axum::response::Redirect;
+    use axum::{extract::State, http::StatusCode, response::Redirect};
+    use html_form_actions::{ActionResult, FieldError, FormValue, HtmlForm};
 
     use super::{AppState, Values};
 
     pub const PATH: &str = "/";
 
-    async fn page_handler(Values(values): Values) -> impl axum::response::IntoResponse {
+    #[derive(HtmlForm)]
+    struct Row {
+        value: i32,
+    }
+
+    fn render_page(values: &[i32], add_value_failure: Option<&add_value::Failure>) -> maud::Markup {
+        let add_value_prefill = add_value_failure
+            .map(|failure| add_value::Prefill::from(&failure.values))
+            .unwrap_or_default();
+
+        let add_value_errors = add_value_failure
+            .map(|failure| failure.errors)
+            .unwrap_or_default();
+
         maud::html! {
             (maud::DOCTYPE)
             html lang="en" {
@@ -40,22 +54,24 @@ mod index_page {
                     style { (maud::PreEscaped(include_str!("style.css"))) }
                 }
                 body {
+                    {
+                        @let update_values::Form { action, .. } = update_values::FORM;
+                        form id="update-values" action=(action) method="post" {}
+                    }
                     ul {
                         @for (index, value) in values.iter().enumerate() {
                             li {
                                 fieldset {
                                     {
-                                        @let update_value::Form { action, index_name, value_name } = update_value::FORM;
-                                        form action=(action) method="post" {
-                                            input type="hidden" name=(index_name) value=(index);
-                                            input type="number" name=(value_name) value=(value);
-                                            input type="submit" value="Update Value";
-                                        }
+                                        @let row::Form { value_input_type, value_step, .. } = row::FORM;
+                                        @let row_names = update_values::FORM.rows().at(index);
+                                        @let row_prefill = row::Prefill { value: Some(value.into()) };
+                                        input form="update-values" type=(value_input_type) step=[value_step] name=(row_names.value_name()) value=[row_prefill.value];
                                     }
                                     {
-                                        @let remove_value::Form { action, index_name } = remove_value::FORM;
+                                        @let remove_value::Form { action, index_name, index_input_type, .. } = remove_value::FORM;
                                         form action=(action) method="post" {
-                                            input type="hidden" name=(index_name) value=(index);
+                                            input type=(index_input_type) name=(index_name) value=(index);
                                             input type="submit" value="Remove Value";
                                         }
                                     }
@@ -63,12 +79,18 @@ mod index_page {
                             }
                         }
                     }
+                    @if !values.is_empty() {
+                        input form="update-values" type="submit" value="Update Values";
+                    }
                     {
-                        @let add_value::Form { action, value_name } = add_value::FORM;
+                        @let add_value::Form { action, value_name, value_input_type, value_step, .. } = add_value::FORM;
                         form action=(action) method="post" {
                             fieldset {
-                                input type="number" name=(value_name) value="0";
+                                input type=(value_input_type) step=[value_step] name=(value_name) value=(add_value_prefill.value.unwrap_or(FormValue::Typed(&0)));
                                 input type="submit" value="Add Value";
+                                @if let Some(error) = add_value_errors.value {
+                                    p { (error) }
+                                }
                             }
                         }
                     }
@@ -77,19 +99,42 @@ mod index_page {
         }
     }
 
-    #[action]
-    async fn add_value(#[form] value: i32, Values(mut values): Values) -> Redirect {
+    async fn page_handler(Values(values): Values) -> maud::Markup {
+        render_page(&values, None)
+    }
+
+    #[action(fail = add_value_failed)]
+    async fn add_value(
+        #[form] value: i32,
+        State(state): State<AppState>,
+    ) -> ActionResult<Redirect, add_value::Errors> {
+        let mut values = state.values.lock().await;
+
+        if values.contains(&value) {
+            return Err(add_value::Errors {
+                value: Some(FieldError::Message("This value is already in the list")),
+            });
+        }
+
         values.push(value);
-        Redirect::to("")
+        Ok(Redirect::to(""))
+    }
+
+    async fn add_value_failed(
+        failure: add_value::Failure<'_>,
+        State(state): State<AppState>,
+    ) -> (StatusCode, maud::Markup) {
+        let values = state.values.lock().await;
+
+        (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            render_page(&values, Some(&failure)),
+        )
     }
 
     #[action]
-    async fn update_value(
-        #[form] index: usize,
-        #[form] value: i32,
-        Values(mut values): Values,
-    ) -> Redirect {
-        if let Some(slot) = values.get_mut(index) {
+    async fn update_values(#[form(nested)] rows: Vec<Row>, Values(mut values): Values) -> Redirect {
+        for (slot, Row { value }) in values.iter_mut().zip(rows) {
             *slot = value;
         }
 
@@ -97,7 +142,10 @@ mod index_page {
     }
 
     #[action]
-    async fn remove_value(#[form] index: usize, Values(mut values): Values) -> Redirect {
+    async fn remove_value(
+        #[form(input_type = "hidden")] index: usize,
+        Values(mut values): Values,
+    ) -> Redirect {
         if index < values.len() {
             values.remove(index);
         }
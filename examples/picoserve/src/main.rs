@@ -24,6 +24,7 @@ impl<'r> picoserve::extract::FromRequestParts<'r, AppState> for Values {
 
 #[html_form_actions::actions(picoserve, state = AppState)]
 mod index_page {
+    use html_form_actions::IdExt;
     use picoserve::response::Redirect;
 
     use super::{AppState, Values};
@@ -44,17 +45,18 @@ mod index_page {
                             li {
                                 fieldset {
                                     {
-                                        @let update_value::Form { action, index_name, value_name } = update_value::FORM;
+                                        @let update_value::Form { action, index_name, index_input_type, value_name, value_id, value_input_type, value_step, .. } = update_value::FORM;
                                         form action=(action) method="post" {
-                                            input type="hidden" name=(index_name) value=(index);
-                                            input type="number" name=(value_name) value=(value);
+                                            input type=(index_input_type) name=(index_name) value=(index);
+                                            label for=(value_id.with_suffix(index)) { "Value " (index) }
+                                            input id=(value_id.with_suffix(index)) type=(value_input_type) step=[value_step] name=(value_name) value=(value);
                                             input type="submit" value="Update Value";
                                         }
                                     }
                                     {
-                                        @let remove_value::Form { action, index_name } = remove_value::FORM;
+                                        @let remove_value::Form { action, index_name, index_input_type, .. } = remove_value::FORM;
                                         form action=(action) method="post" {
-                                            input type="hidden" name=(index_name) value=(index);
+                                            input type=(index_input_type) name=(index_name) value=(index);
                                             input type="submit" value="Remove Value";
                                         }
                                     }
@@ -63,10 +65,11 @@ mod index_page {
                         }
                     }
                     {
-                        @let add_value::Form { action, value_name } = add_value::FORM;
+                        @let add_value::Form { action, value_name, value_id, value_input_type, value_step, .. } = add_value::FORM;
                         form action=(action) method="post" {
                             fieldset {
-                                input type="number" name=(value_name) value="0";
+                                label for=(value_id) { "New Value" }
+                                input id=(value_id) type=(value_input_type) step=[value_step] name=(value_name) value="0";
                                 input type="submit" value="Add Value";
                             }
                         }
@@ -105,7 +108,7 @@ mod index_page {
 
     #[action]
     async fn update_value(
-        #[form] index: usize,
+        #[form(input_type = "hidden")] index: usize,
         #[form] value: i32,
         Values(mut values): Values,
     ) -> Redirect {
@@ -117,7 +120,10 @@ mod index_page {
     }
 
     #[action]
-    async fn remove_value(#[form] index: usize, Values(mut values): Values) -> Redirect {
+    async fn remove_value(
+        #[form(input_type = "hidden")] index: usize,
+        Values(mut values): Values,
+    ) -> Redirect {
         if index < values.len() {
             values.remove(index);
         }
@@ -135,6 +141,84 @@ mod index_page {
     }
 }
 
+/// A timer for picoserve using tokio.
+///
+/// picoserve's `tokio` feature replaces the generic `serve_with_state` with one which only accepts a `TcpStream`,
+/// but the test client of `html_form_actions` serves requests over an in-memory connection using the generic function,
+/// so this example provides its own timer and socket instead.
+struct TokioTimer;
+
+impl picoserve::Timer for TokioTimer {
+    type Duration = Duration;
+    type TimeoutError = tokio::time::error::Elapsed;
+
+    async fn run_with_timeout<F: Future>(
+        &mut self,
+        duration: Duration,
+        future: F,
+    ) -> Result<F::Output, Self::TimeoutError> {
+        tokio::time::timeout(duration, future).await
+    }
+}
+
+/// An error reading from or writing to a connection, which is printed when the connection fails.
+#[derive(Debug)]
+struct IoError(#[allow(dead_code)] std::io::Error);
+
+impl picoserve::io::Error for IoError {
+    fn kind(&self) -> picoserve::io::ErrorKind {
+        picoserve::io::ErrorKind::Other
+    }
+}
+
+/// Half of a tokio TCP connection.
+struct Half<T>(T);
+
+impl<T> picoserve::io::ErrorType for Half<T> {
+    type Error = IoError;
+}
+
+impl<T: tokio::io::AsyncRead + Unpin> picoserve::io::Read for Half<T> {
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, IoError> {
+        tokio::io::AsyncReadExt::read(&mut self.0, buf)
+            .await
+            .map_err(IoError)
+    }
+}
+
+impl<T: tokio::io::AsyncWrite + Unpin> picoserve::io::Write for Half<T> {
+    async fn write(&mut self, buf: &[u8]) -> Result<usize, IoError> {
+        tokio::io::AsyncWriteExt::write(&mut self.0, buf)
+            .await
+            .map_err(IoError)
+    }
+}
+
+/// A tokio TCP connection, served by picoserve.
+struct Connection(tokio::net::TcpStream);
+
+impl picoserve::io::Socket for Connection {
+    type Error = IoError;
+    type ReadHalf<'a> = Half<tokio::net::tcp::ReadHalf<'a>>;
+    type WriteHalf<'a> = Half<tokio::net::tcp::WriteHalf<'a>>;
+
+    fn split(&mut self) -> (Self::ReadHalf<'_>, Self::WriteHalf<'_>) {
+        let (read_half, write_half) = self.0.split();
+
+        (Half(read_half), Half(write_half))
+    }
+
+    async fn shutdown<T: picoserve::Timer>(
+        mut self,
+        _timeouts: &picoserve::Timeouts<T::Duration>,
+        _timer: &mut T,
+    ) -> Result<(), picoserve::Error<IoError>> {
+        tokio::io::AsyncWriteExt::shutdown(&mut self.0)
+            .await
+            .map_err(|error| picoserve::Error::Write(IoError(error)))
+    }
+}
+
 #[tokio::main(flavor = "current_thread")]
 async fn main() {
     let port = 8000;
@@ -171,8 +255,15 @@ async fn main() {
                 let config = config.clone();
 
                 tokio::task::spawn_local(async move {
-                    match picoserve::serve_with_state(&app, &config, &mut [0; 2048], stream, &state)
-                        .await
+                    match picoserve::serve_with_state(
+                        &app,
+                        TokioTimer,
+                        &config,
+                        &mut [0; 2048],
+                        Connection(stream),
+                        &state,
+                    )
+                    .await
                     {
                         Ok(handled_requests_count) => {
                             println!(
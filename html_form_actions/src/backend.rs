@@ -0,0 +1,45 @@
+use core::future::Future;
+
+/// A web framework or HTTP stack which the actions of a module can be served with,
+/// using `#[actions(backend(with = MyBackend))]`.
+///
+/// The generated handler reads the action from the query of the request,
+/// extracts the other parameters of the action using [`FromActionRequest`], reads and parses the body,
+/// and converts the value returned by the action into a response using [`IntoActionResponse`].
+pub trait ActionBackend {
+    /// The request passed to the generated handler, which should contain any state needed by the actions.
+    type Request;
+    /// The response returned by the generated handler.
+    type Response;
+    /// The body of a request, which is decoded in place,
+    /// so may borrow the buffer of the request, as the other parameters have been extracted by then.
+    type Body<'r>: AsMut<[u8]>;
+
+    /// The raw query of the request, which contains the action, such as `/my_action`.
+    fn query(request: &Self::Request) -> Option<&str>;
+
+    /// Read the body of the request, or respond with the reason it could not be read.
+    fn body(
+        request: &mut Self::Request,
+    ) -> impl Future<Output = Result<Self::Body<'_>, Self::Response>>;
+
+    /// The response if the query does not contain an action of the module.
+    fn not_found() -> Self::Response;
+
+    /// The response if the body cannot be parsed, or if a submission is rejected and there is no `fail` or `reject` handler.
+    fn bad_request(reason: &'static str) -> Self::Response;
+}
+
+/// A non-form parameter of an action, which is extracted from the request by the handler generated for the backend `B`.
+pub trait FromActionRequest<B: ActionBackend>: Sized {
+    /// Extract the parameter from `request`, or respond with the reason it could not be extracted.
+    fn from_action_request(
+        request: &mut B::Request,
+    ) -> impl Future<Output = Result<Self, B::Response>>;
+}
+
+/// A value returned by an action or by a `fail` or `reject` handler, which is converted into a response of the backend `B`.
+pub trait IntoActionResponse<B: ActionBackend> {
+    /// Convert the value into a response.
+    fn into_action_response(self) -> B::Response;
+}
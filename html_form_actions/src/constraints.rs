@@ -0,0 +1,181 @@
+use crate::FieldError;
+
+/// Validation constraints on the submitted value of a form field, matching the HTML constraint validation attributes.
+///
+/// Constraints are declared using `#[form(...)]`, enforced by the generated handlers, and exposed by each action's `FORM`
+/// so that the HTML input can declare the same constraints.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Constraints {
+    /// The value must be submitted and not empty.
+    pub required: bool,
+    /// The minimum value.
+    pub min: Option<&'static str>,
+    /// The maximum value.
+    pub max: Option<&'static str>,
+    /// The value must be a whole number of steps from `min`, or from zero if there is no `min`.
+    pub step: Option<&'static str>,
+    /// The minimum number of characters.
+    pub min_length: Option<usize>,
+    /// The maximum number of characters.
+    pub max_length: Option<usize>,
+    /// A regular expression which the entire value must match.
+    #[cfg(feature = "pattern")]
+    pub pattern: Option<&'static Pattern>,
+}
+
+/// A regular expression which the entire value of a field must match, which is compiled when it is first used.
+///
+/// The generated handlers declare a `static` pattern for each field, so that it is only compiled once.
+#[cfg(feature = "pattern")]
+pub struct Pattern {
+    source: &'static str,
+    regex: std::sync::OnceLock<Option<regex_lite::Regex>>,
+}
+
+#[cfg(feature = "pattern")]
+impl Pattern {
+    /// A pattern matching the regular expression `source`, such as `[a-z]+`.
+    pub const fn new(source: &'static str) -> Self {
+        Self {
+            source,
+            regex: std::sync::OnceLock::new(),
+        }
+    }
+
+    /// The regular expression, as declared.
+    pub const fn as_str(&self) -> &'static str {
+        self.source
+    }
+
+    /// Whether the entire `value` matches the pattern.
+    ///
+    /// No value matches an invalid pattern, which the `actions` macro rejects when the pattern is declared.
+    pub fn is_match(&self, value: &str) -> bool {
+        self.regex
+            .get_or_init(|| regex_lite::Regex::new(&alloc::format!("^(?:{})$", self.source)).ok())
+            .as_ref()
+            .is_some_and(|regex| regex.is_match(value))
+    }
+}
+
+#[cfg(feature = "pattern")]
+impl core::fmt::Debug for Pattern {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("Pattern").field(&self.source).finish()
+    }
+}
+
+#[cfg(feature = "pattern")]
+impl PartialEq for Pattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+#[cfg(feature = "pattern")]
+impl Eq for Pattern {}
+
+/// Compare numerically if both values are numbers, and otherwise lexically, which is correct for dates and times.
+fn compare(value: &str, bound: &str) -> Option<core::cmp::Ordering> {
+    match (value.parse::<f64>(), bound.parse::<f64>()) {
+        (Ok(value), Ok(bound)) => value.partial_cmp(&bound),
+        _ => Some(value.cmp(bound)),
+    }
+}
+
+fn is_whole_number_of_steps(value: &str, base: Option<&str>, step: &str) -> bool {
+    let (Ok(value), Ok(base), Ok(step)) = (
+        value.parse::<f64>(),
+        base.map_or(Ok(0.0), str::parse::<f64>),
+        step.parse::<f64>(),
+    ) else {
+        return true;
+    };
+
+    let steps = (value - base) / step;
+
+    // `f64::round` is not available in `core`
+    let nearest_whole_number_of_steps = if steps < 0.0 {
+        (steps - 0.5) as i64
+    } else {
+        (steps + 0.5) as i64
+    } as f64;
+
+    (steps - nearest_whole_number_of_steps).abs() <= 1e-9 * steps.abs().max(1.0)
+}
+
+impl Constraints {
+    /// No constraints.
+    pub const NONE: Self = Self {
+        required: false,
+        min: None,
+        max: None,
+        step: None,
+        min_length: None,
+        max_length: None,
+        #[cfg(feature = "pattern")]
+        pattern: None,
+    };
+
+    /// Check the submitted value against the constraints.
+    ///
+    /// As in HTML, only `required` applies to a missing or empty value.
+    pub fn check(&self, value: Option<&str>) -> Result<(), FieldError> {
+        let Some(value) = value.filter(|value| !value.is_empty()) else {
+            return if self.required {
+                Err(FieldError::Missing)
+            } else {
+                Ok(())
+            };
+        };
+
+        if let Some(min_length) = self.min_length
+            && value.chars().count() < min_length
+        {
+            return Err(FieldError::TooShort { min_length });
+        }
+
+        if let Some(max_length) = self.max_length
+            && value.chars().count() > max_length
+        {
+            return Err(FieldError::TooLong { max_length });
+        }
+
+        #[cfg(feature = "pattern")]
+        if let Some(pattern) = self.pattern
+            && !pattern.is_match(value)
+        {
+            return Err(FieldError::PatternMismatch);
+        }
+
+        if let Some(min) = self.min
+            && compare(value, min) == Some(core::cmp::Ordering::Less)
+        {
+            return Err(FieldError::RangeUnderflow { min });
+        }
+
+        if let Some(max) = self.max
+            && compare(value, max) == Some(core::cmp::Ordering::Greater)
+        {
+            return Err(FieldError::RangeOverflow { max });
+        }
+
+        if let Some(step) = self.step.filter(|&step| step != "any")
+            && !is_whole_number_of_steps(value, self.min, step)
+        {
+            return Err(FieldError::StepMismatch { step });
+        }
+
+        Ok(())
+    }
+
+    /// Check each submitted value of a field against the constraints.
+    ///
+    /// If no values were submitted, this is the same as checking a missing value.
+    pub fn check_all(&self, values: &[&str]) -> Result<(), FieldError> {
+        match values {
+            [] => self.check(None),
+            values => values.iter().try_for_each(|&value| self.check(Some(value))),
+        }
+    }
+}
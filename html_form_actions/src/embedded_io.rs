@@ -0,0 +1,437 @@
+//! A backend which serves actions over any [`embedded_io_async`] connection, for firmware which isn't built on picoserve,
+//! such as when using [`edge-http`](https://docs.rs/edge-http) or a bare TCP socket.
+//!
+//! [`Request::read`] reads the request line, headers and body of a request into a fixed-capacity [`heapless`] buffer,
+//! `#[actions(embedded_io)]` generates a handler which takes the [`Request`] and returns a [`Response`],
+//! and [`Response::write`] writes the response to the connection. No allocator is required.
+//!
+//! The connection is typically a `&mut` reference to a socket, such as `Request::<_, 1024>::read(&mut socket)`,
+//! so that the response can be written to the same socket once the request has been handled.
+//!
+//! Actions may return a `&'static str`, a `heapless::String<N>` with the same capacity as the request,
+//! a `(u16, T)` pair of a status code and either of those,
+//! or a [`Response`], such as [`Response::see_other`] to redirect to the page after a successful submission.
+//! Other parameters of the actions must implement [`FromActionRequest`](crate::FromActionRequest), which can read the headers of the [`Request`].
+
+use core::{fmt::Write as _, marker::PhantomData};
+
+pub use embedded_io_async::{Read, Write};
+
+use crate::{ActionBackend, IntoActionResponse};
+
+/// The maximum number of extra headers of a [`Response`].
+const MAX_HEADERS: usize = 4;
+
+/// The reason a [`Request`] could not be read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadError<E> {
+    /// The connection failed.
+    Io(E),
+    /// The connection was closed before the whole request was read.
+    UnexpectedEof,
+    /// The request line and headers are longer than the capacity of the request.
+    HeadTooLarge,
+    /// The body is longer than the capacity of the request which remains after the head.
+    BodyTooLarge,
+    /// The request line or headers are malformed.
+    InvalidHead,
+    /// The request has a `Transfer-Encoding` header, such as `chunked`, rather than a `Content-Length`.
+    UnsupportedTransferEncoding,
+}
+
+impl<E> ReadError<E> {
+    /// The response to send if the request could not be read, or `None` if the connection failed or was closed.
+    pub fn response<const N: usize>(&self) -> Option<Response<N>> {
+        match self {
+            Self::Io(_) | Self::UnexpectedEof => None,
+            Self::HeadTooLarge => Some(Response::new(431, "Request Header Fields Too Large")),
+            Self::BodyTooLarge => Some(Response::new(413, "Content Too Large")),
+            Self::InvalidHead => Some(Response::new(400, "Bad Request")),
+            Self::UnsupportedTransferEncoding => Some(Response::new(411, "Length Required")),
+        }
+    }
+}
+
+/// A request read from a connection of type `R`, such as `&mut TcpSocket`,
+/// whose head and body together may be at most `N` bytes long.
+pub struct Request<R, const N: usize> {
+    connection: R,
+    /// The head, which is valid UTF-8, followed by a blank line and the body.
+    buffer: heapless::Vec<u8, N>,
+    head_length: usize,
+}
+
+impl<R: Read, const N: usize> Request<R, N> {
+    /// Read the request line, the headers and the body, whose length is given by the `Content-Length` header.
+    ///
+    /// Any bytes after the body, such as from a pipelined request, are discarded.
+    /// Requests with a `Transfer-Encoding` header, such as `chunked`, are rejected, as their length isn't known in advance.
+    pub async fn read(mut connection: R) -> Result<Self, ReadError<R::Error>> {
+        let mut buffer = heapless::Vec::<u8, N>::new();
+        buffer
+            .resize_default(N)
+            .unwrap_or_else(|_| unreachable!("the buffer has a capacity of N"));
+
+        let mut length = 0;
+
+        let head_length = loop {
+            if let Some(position) = buffer[..length]
+                .windows(4)
+                .position(|window| window == b"\r\n\r\n")
+            {
+                break position;
+            }
+
+            if length == N {
+                return Err(ReadError::HeadTooLarge);
+            }
+
+            match connection.read(&mut buffer[length..]).await {
+                Ok(0) => return Err(ReadError::UnexpectedEof),
+                Ok(read) => length += read,
+                Err(error) => return Err(ReadError::Io(error)),
+            }
+        };
+
+        core::str::from_utf8(&buffer[..head_length]).map_err(|_| ReadError::InvalidHead)?;
+
+        let mut request = Self {
+            connection,
+            buffer,
+            head_length,
+        };
+
+        let body_end = request
+            .body_start()
+            .checked_add(request.content_length()?)
+            .filter(|&body_end| body_end <= N)
+            .ok_or(ReadError::BodyTooLarge)?;
+
+        while length < body_end {
+            match request
+                .connection
+                .read(&mut request.buffer[length..body_end])
+                .await
+            {
+                Ok(0) => return Err(ReadError::UnexpectedEof),
+                Ok(read) => length += read,
+                Err(error) => return Err(ReadError::Io(error)),
+            }
+        }
+
+        request.buffer.truncate(body_end);
+
+        Ok(request)
+    }
+
+    /// Check that the head is well-formed, and return the length of the body, which is 0 if there is no `Content-Length` header.
+    fn content_length(&self) -> Result<usize, ReadError<R::Error>> {
+        let mut request_line = self.request_line().split(' ');
+
+        let (Some(_), Some(_), Some(_), None) = (
+            request_line.next(),
+            request_line.next(),
+            request_line.next(),
+            request_line.next(),
+        ) else {
+            return Err(ReadError::InvalidHead);
+        };
+
+        if self.headers().any(|header| header.is_none()) {
+            return Err(ReadError::InvalidHead);
+        }
+
+        if self.header("Transfer-Encoding").is_some() {
+            return Err(ReadError::UnsupportedTransferEncoding);
+        }
+
+        self.header("Content-Length")
+            .map_or(Ok(0), |length| length.parse())
+            .map_err(|_| ReadError::InvalidHead)
+    }
+}
+
+impl<R, const N: usize> Request<R, N> {
+    fn head(&self) -> &str {
+        // The head is checked when the request is read
+        core::str::from_utf8(&self.buffer[..self.head_length]).unwrap_or_default()
+    }
+
+    /// The start of the body, after the head and the blank line which ends it.
+    fn body_start(&self) -> usize {
+        self.head_length + 4
+    }
+
+    fn request_line(&self) -> &str {
+        self.head().split("\r\n").next().unwrap_or_default()
+    }
+
+    fn headers(&self) -> impl Iterator<Item = Option<(&str, &str)>> {
+        self.head()
+            .split("\r\n")
+            .skip(1)
+            .map(|header| header.split_once(':'))
+    }
+
+    /// The method of the request, such as `POST`.
+    pub fn method(&self) -> &str {
+        self.request_line().split(' ').next().unwrap_or_default()
+    }
+
+    /// The target of the request, such as `/page?/my_action`.
+    pub fn target(&self) -> &str {
+        self.request_line().split(' ').nth(1).unwrap_or_default()
+    }
+
+    /// The path of the request, such as `/page`.
+    pub fn path(&self) -> &str {
+        let target = self.target();
+
+        target.split_once('?').map_or(target, |(path, _)| path)
+    }
+
+    /// The raw query of the request, such as `/my_action`.
+    pub fn query(&self) -> Option<&str> {
+        self.target().split_once('?').map(|(_, query)| query)
+    }
+
+    /// The value of the first header called `name`, which is compared case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers()
+            .flatten()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.trim())
+    }
+
+    /// The body of the request.
+    pub fn body(&self) -> &[u8] {
+        &self.buffer[self.body_start()..]
+    }
+
+    /// The body of the request, which the generated handler decodes in place.
+    pub fn body_mut(&mut self) -> &mut [u8] {
+        let body_start = self.body_start();
+
+        &mut self.buffer[body_start..]
+    }
+
+    /// The connection which the request was read from.
+    pub fn connection(&mut self) -> &mut R {
+        &mut self.connection
+    }
+
+    /// Take the connection which the request was read from.
+    pub fn into_connection(self) -> R {
+        self.connection
+    }
+}
+
+/// The reason a header could not be added to a [`Response`], which has at most 4 extra headers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TooManyHeaders;
+
+enum ResponseBody<const N: usize> {
+    Static(&'static str),
+    Owned(heapless::String<N>),
+}
+
+/// A plain text response, whose body is either a `&'static str` or a `heapless::String<N>`.
+pub struct Response<const N: usize> {
+    status: u16,
+    content_type: &'static str,
+    headers: heapless::Vec<(&'static str, &'static str), MAX_HEADERS>,
+    body: ResponseBody<N>,
+}
+
+impl<const N: usize> Response<N> {
+    fn with_body(status: u16, body: ResponseBody<N>) -> Self {
+        Self {
+            status,
+            content_type: "text/plain; charset=utf-8",
+            headers: heapless::Vec::new(),
+            body,
+        }
+    }
+
+    /// A response with the given `status` and `body`.
+    pub fn new(status: u16, body: &'static str) -> Self {
+        Self::with_body(status, ResponseBody::Static(body))
+    }
+
+    /// A response with the given `status` and a formatted `body`.
+    pub fn from_string(status: u16, body: heapless::String<N>) -> Self {
+        Self::with_body(status, ResponseBody::Owned(body))
+    }
+
+    /// A `303 See Other` response, which redirects the browser to `location`, such as to the page after a successful submission.
+    pub fn see_other(location: &'static str) -> Self {
+        let mut response = Self::new(303, "");
+
+        // A new response has no extra headers, so there is room for the location
+        let _ = response.headers.push(("Location", location));
+
+        response
+    }
+
+    /// Replace the `Content-Type` header, which is `text/plain; charset=utf-8` by default.
+    pub fn with_content_type(self, content_type: &'static str) -> Self {
+        Self {
+            content_type,
+            ..self
+        }
+    }
+
+    /// Add a header to the response, or return [`TooManyHeaders`] if the response already has 4 extra headers.
+    pub fn with_header(
+        mut self,
+        name: &'static str,
+        value: &'static str,
+    ) -> Result<Self, TooManyHeaders> {
+        self.headers
+            .push((name, value))
+            .map_err(|_| TooManyHeaders)?;
+
+        Ok(self)
+    }
+
+    /// The status code of the response.
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// The value of the first extra header called `name`, which is compared case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| *value)
+    }
+
+    /// The body of the response.
+    pub fn body(&self) -> &str {
+        match &self.body {
+            ResponseBody::Static(body) => body,
+            ResponseBody::Owned(body) => body,
+        }
+    }
+
+    /// Write the response to `connection`, and ask the client to close the connection.
+    pub async fn write<W: Write>(&self, mut connection: W) -> Result<(), W::Error> {
+        let body = self.body();
+
+        let mut status_line = heapless::String::<64>::new();
+        let mut content_length = heapless::String::<20>::new();
+
+        // Neither can overflow, as the reasons and lengths are short
+        let _ = write!(
+            status_line,
+            "HTTP/1.1 {} {}\r\n",
+            self.status,
+            reason(self.status)
+        );
+        let _ = write!(content_length, "{}", body.len());
+
+        connection.write_all(status_line.as_bytes()).await?;
+
+        for (name, value) in [
+            ("Content-Type", self.content_type),
+            ("Content-Length", content_length.as_str()),
+            ("Connection", "close"),
+        ]
+        .into_iter()
+        .chain(self.headers.iter().copied())
+        {
+            for part in [name, ": ", value, "\r\n"] {
+                connection.write_all(part.as_bytes()).await?;
+            }
+        }
+
+        connection.write_all(b"\r\n").await?;
+        connection.write_all(body.as_bytes()).await?;
+
+        connection.flush().await
+    }
+}
+
+/// The reason phrase of common status codes, which is optional in HTTP/1.1.
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        204 => "No Content",
+        303 => "See Other",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        409 => "Conflict",
+        411 => "Length Required",
+        413 => "Content Too Large",
+        422 => "Unprocessable Content",
+        431 => "Request Header Fields Too Large",
+        500 => "Internal Server Error",
+        503 => "Service Unavailable",
+        _ => "",
+    }
+}
+
+/// The [`ActionBackend`] of handlers generated by `#[actions(embedded_io)]`,
+/// which handles a [`Request`] read from a connection of type `R` with a capacity of `N` bytes.
+pub struct EmbeddedIo<R, const N: usize>(PhantomData<R>);
+
+impl<R, const N: usize> ActionBackend for EmbeddedIo<R, N> {
+    type Request = Request<R, N>;
+    type Response = Response<N>;
+    type Body<'r> = &'r mut [u8];
+
+    fn query(request: &Self::Request) -> Option<&str> {
+        request.query()
+    }
+
+    /// The body within the buffer of the request, as the whole body has already been read.
+    async fn body(request: &mut Self::Request) -> Result<Self::Body<'_>, Self::Response> {
+        Ok(request.body_mut())
+    }
+
+    fn not_found() -> Self::Response {
+        Response::new(404, "Action Not Found")
+    }
+
+    fn bad_request(reason: &'static str) -> Self::Response {
+        Response::new(400, reason)
+    }
+}
+
+impl<R, const N: usize> IntoActionResponse<EmbeddedIo<R, N>> for Response<N> {
+    fn into_action_response(self) -> Response<N> {
+        self
+    }
+}
+
+impl<R, const N: usize> IntoActionResponse<EmbeddedIo<R, N>> for &'static str {
+    fn into_action_response(self) -> Response<N> {
+        Response::new(200, self)
+    }
+}
+
+impl<R, const N: usize> IntoActionResponse<EmbeddedIo<R, N>> for heapless::String<N> {
+    fn into_action_response(self) -> Response<N> {
+        Response::from_string(200, self)
+    }
+}
+
+impl<R, const N: usize> IntoActionResponse<EmbeddedIo<R, N>> for (u16, &'static str) {
+    fn into_action_response(self) -> Response<N> {
+        let (status, body) = self;
+
+        Response::new(status, body)
+    }
+}
+
+impl<R, const N: usize> IntoActionResponse<EmbeddedIo<R, N>> for (u16, heapless::String<N>) {
+    fn into_action_response(self) -> Response<N> {
+        let (status, body) = self;
+
+        Response::from_string(status, body)
+    }
+}
@@ -0,0 +1,103 @@
+use core::fmt;
+
+/// The reason a single form field was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldError {
+    /// The field was not submitted.
+    Missing,
+    /// The submitted value could not be parsed.
+    Invalid,
+    /// The submitted value has fewer characters than `min_length`.
+    TooShort { min_length: usize },
+    /// The submitted value has more characters than `max_length`.
+    TooLong { max_length: usize },
+    /// The submitted value does not match `pattern`.
+    PatternMismatch,
+    /// The submitted value is less than `min`.
+    RangeUnderflow { min: &'static str },
+    /// The submitted value is greater than `max`.
+    RangeOverflow { max: &'static str },
+    /// The submitted value is not a whole number of steps.
+    StepMismatch { step: &'static str },
+    /// More than `max_values` values were submitted for a multi-valued field.
+    TooManyValues { max_values: usize },
+    /// The action rejected the value, with a message to show to the user.
+    Message(&'static str),
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing => f.write_str("This field is required"),
+            Self::Invalid => f.write_str("This value is invalid"),
+            Self::TooShort { min_length } => write!(f, "Use at least {min_length} characters"),
+            Self::TooLong { max_length } => write!(f, "Use at most {max_length} characters"),
+            Self::PatternMismatch => f.write_str("This value does not match the requested format"),
+            Self::RangeUnderflow { min } => write!(f, "This value must be at least {min}"),
+            Self::RangeOverflow { max } => write!(f, "This value must be at most {max}"),
+            Self::StepMismatch { step } => write!(f, "This value must be in steps of {step}"),
+            Self::TooManyValues { max_values } => write!(f, "Select at most {max_values} values"),
+            Self::Message(message) => f.write_str(message),
+        }
+    }
+}
+
+/// A rejected form submission, containing the submitted values and the reason each field was rejected.
+///
+/// Each action module contains a `Failure` alias with the action's `Values` and `Errors`,
+/// which is passed to the action's `fail` handler so that the page can be re-rendered with error messages and prefilled inputs.
+#[derive(Debug, Clone, Default)]
+pub struct Failure<V, E> {
+    /// The raw submitted values.
+    pub values: V,
+    /// The reason each field was rejected.
+    pub errors: E,
+}
+
+/// The return type of an action with a `fail` handler.
+///
+/// Returning `Err(errors)` passes the errors, along with the submitted values, to the `fail` handler.
+pub type ActionResult<T, E> = Result<T, E>;
+
+/// A rejected form submission of any action, passed to the module's `reject` handler.
+#[derive(Debug, Clone, Copy)]
+pub struct Rejection<'a> {
+    /// The action, as used in the "action" attribute of the HTML form.
+    pub action: &'static str,
+    /// Each `#[form]` field of the action.
+    pub fields: &'a [RejectedField<'a>],
+}
+
+impl Rejection<'_> {
+    /// The fields which were rejected, along with the reason each was rejected.
+    pub fn errors(&self) -> impl Iterator<Item = (&RejectedField<'_>, FieldError)> {
+        self.fields
+            .iter()
+            .filter_map(|field| Some((field, field.error?)))
+    }
+}
+
+impl fmt::Display for Rejection<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, (RejectedField { name, .. }, error)) in self.errors().enumerate() {
+            if index > 0 {
+                f.write_str("\n")?;
+            }
+
+            write!(f, "{name}: {error}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A single field of a [`Rejection`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RejectedField<'a> {
+    /// The name of the field, as used in the "name" attribute of the HTML input.
+    pub name: &'static str,
+    /// The raw submitted value, or the first submitted value of a multi-valued field.
+    pub value: Option<&'a str>,
+    /// The reason the field was rejected, if it was.
+    pub error: Option<FieldError>,
+}
@@ -0,0 +1,448 @@
+use core::{fmt, ops::ControlFlow};
+
+use crate::FieldError;
+
+/// A type which can be parsed from a single submitted form value.
+///
+/// Implement this trait to use your own types as `#[form]` parameters.
+pub trait FromFormValue<'r>: Sized {
+    /// The "type" attribute of the HTML input used to enter the value.
+    const INPUT_TYPE: &'static str = "text";
+
+    /// The "step" attribute of the HTML input used to enter the value, unless overridden by `#[form(step = ...)]`.
+    const STEP: Option<&'static str> = None;
+
+    /// The `(value, label)` pairs of the options of a `<select>` or group of radio inputs, if the value must be one of them.
+    const OPTIONS: &'static [(&'static str, &'static str)] = &[];
+
+    /// The "maxlength" attribute of the HTML input used to enter the value, unless overridden by `#[form(max_length = ...)]`,
+    /// such as the capacity of a fixed-capacity string.
+    const MAX_LENGTH: Option<usize> = None;
+
+    /// Parse the submitted value.
+    fn from_form_value(value: &'r str) -> Result<Self, FieldError>;
+
+    /// The value used if no value was submitted, such as for an unchecked checkbox.
+    ///
+    /// By default, a missing value is an error.
+    fn from_missing_value() -> Result<Self, FieldError> {
+        Err(FieldError::Missing)
+    }
+}
+
+/// A type which can be written as a form value, such as to prefill an HTML input with the current value on an edit page.
+///
+/// This is the inverse of [`FromFormValue`], so the written value must be parsed as the same value.
+pub trait ToFormValue {
+    /// Write the value as it would be submitted.
+    fn fmt_form_value(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+}
+
+macro_rules! from_str_form_values {
+    ($input_type:literal, $step:expr => $($ty:ty)*) => {
+        $(
+            impl FromFormValue<'_> for $ty {
+                const INPUT_TYPE: &'static str = $input_type;
+                const STEP: Option<&'static str> = $step;
+
+                fn from_form_value(value: &str) -> Result<Self, FieldError> {
+                    value.parse().map_err(|_| FieldError::Invalid)
+                }
+            }
+
+            impl ToFormValue for $ty {
+                fn fmt_form_value(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    fmt::Display::fmt(self, f)
+                }
+            }
+        )*
+    };
+}
+
+from_str_form_values! {
+    "number", Some("1") =>
+    i8 i16 i32 i64 i128 isize
+    u8 u16 u32 u64 u128 usize
+}
+
+from_str_form_values! { "number", Some("any") => f32 f64 }
+
+/// A checkbox, which is submitted as "on" if checked, and not submitted at all if unchecked.
+impl FromFormValue<'_> for bool {
+    const INPUT_TYPE: &'static str = "checkbox";
+
+    fn from_form_value(value: &str) -> Result<Self, FieldError> {
+        match value {
+            "on" | "true" => Ok(true),
+            "off" | "false" => Ok(false),
+            _ => Err(FieldError::Invalid),
+        }
+    }
+
+    fn from_missing_value() -> Result<Self, FieldError> {
+        Ok(false)
+    }
+}
+
+impl ToFormValue for bool {
+    fn fmt_form_value(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(if *self { "true" } else { "false" })
+    }
+}
+
+from_str_form_values! { "text", None => char }
+
+#[cfg(feature = "alloc")]
+impl FromFormValue<'_> for alloc::string::String {
+    fn from_form_value(value: &str) -> Result<Self, FieldError> {
+        Ok(value.into())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl ToFormValue for alloc::string::String {
+    fn fmt_form_value(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self)
+    }
+}
+
+impl ToFormValue for str {
+    fn fmt_form_value(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self)
+    }
+}
+
+impl<T: ToFormValue + ?Sized> ToFormValue for &T {
+    fn fmt_form_value(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        T::fmt_form_value(self, f)
+    }
+}
+
+/// The submitted value, borrowed from the request body, which is decoded in place.
+impl<'r> FromFormValue<'r> for &'r str {
+    fn from_form_value(value: &'r str) -> Result<Self, FieldError> {
+        Ok(value)
+    }
+}
+
+/// The submitted value, which is always borrowed from the request body.
+#[cfg(feature = "alloc")]
+impl<'r> FromFormValue<'r> for alloc::borrow::Cow<'r, str> {
+    fn from_form_value(value: &'r str) -> Result<Self, FieldError> {
+        Ok(Self::Borrowed(value))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl ToFormValue for alloc::borrow::Cow<'_, str> {
+    fn fmt_form_value(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self)
+    }
+}
+
+/// The submitted value, which is rejected as too long if it has more than `N / 4` characters.
+///
+/// Like the `max_length` constraint, the maximum length is counted in characters, and is `N / 4`,
+/// as each character is at most four bytes, so any value of that many characters fits in `N` bytes.
+/// The same limit is used as the "maxlength" of the HTML input, so the browser and the server agree.
+#[cfg(feature = "heapless")]
+impl<const N: usize> FromFormValue<'_> for heapless::String<N> {
+    const MAX_LENGTH: Option<usize> = Some(N / 4);
+
+    fn from_form_value(value: &str) -> Result<Self, FieldError> {
+        let too_long = FieldError::TooLong { max_length: N / 4 };
+
+        if value.chars().count() > N / 4 {
+            return Err(too_long);
+        }
+
+        value.try_into().map_err(|_| too_long)
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<const N: usize> ToFormValue for heapless::String<N> {
+    fn fmt_form_value(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl FromFormValue<'_> for chrono::NaiveDate {
+    const INPUT_TYPE: &'static str = "date";
+
+    fn from_form_value(value: &str) -> Result<Self, FieldError> {
+        value.parse().map_err(|_| FieldError::Invalid)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl ToFormValue for chrono::NaiveDate {
+    fn fmt_form_value(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use chrono::Datelike;
+
+        write!(
+            f,
+            "{:04}-{:02}-{:02}",
+            self.year(),
+            self.month(),
+            self.day()
+        )
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl FromFormValue<'_> for chrono::NaiveTime {
+    const INPUT_TYPE: &'static str = "time";
+
+    fn from_form_value(value: &str) -> Result<Self, FieldError> {
+        value.parse().map_err(|_| FieldError::Invalid)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl ToFormValue for chrono::NaiveTime {
+    fn fmt_form_value(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use chrono::Timelike;
+
+        write!(
+            f,
+            "{:02}:{:02}:{:02}",
+            self.hour(),
+            self.minute(),
+            self.second()
+        )
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl FromFormValue<'_> for chrono::NaiveDateTime {
+    const INPUT_TYPE: &'static str = "datetime-local";
+
+    fn from_form_value(value: &str) -> Result<Self, FieldError> {
+        // Browsers omit the seconds if they are zero
+        value
+            .parse()
+            .or_else(|_| Self::parse_from_str(value, "%Y-%m-%dT%H:%M"))
+            .map_err(|_| FieldError::Invalid)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl ToFormValue for chrono::NaiveDateTime {
+    fn fmt_form_value(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.date().fmt_form_value(f)?;
+        f.write_str("T")?;
+        self.time().fmt_form_value(f)
+    }
+}
+
+/// The raw submitted values of a form field, borrowed from the request body.
+///
+/// This is `Option<&str>` for fields with a single value, and a collection such as `Vec<&str>` for multi-valued fields.
+pub trait FieldValues<'r>: Default + Clone + fmt::Debug {
+    /// Record a submitted value.
+    fn push(&mut self, value: &'r str) -> Result<(), FieldError>;
+
+    /// The submitted values, in the order they were submitted.
+    fn as_slice(&self) -> &[&'r str];
+}
+
+/// If a single valued field is submitted more than once, the last value is used.
+impl<'r> FieldValues<'r> for Option<&'r str> {
+    fn push(&mut self, value: &'r str) -> Result<(), FieldError> {
+        *self = Some(value);
+
+        Ok(())
+    }
+
+    fn as_slice(&self) -> &[&'r str] {
+        Option::as_slice(self)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'r> FieldValues<'r> for alloc::vec::Vec<&'r str> {
+    fn push(&mut self, value: &'r str) -> Result<(), FieldError> {
+        self.push(value);
+
+        Ok(())
+    }
+
+    fn as_slice(&self) -> &[&'r str] {
+        self
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<'r, const N: usize> FieldValues<'r> for heapless::Vec<&'r str, N> {
+    fn push(&mut self, value: &'r str) -> Result<(), FieldError> {
+        self.push(value)
+            .map_err(|_| FieldError::TooManyValues { max_values: N })
+    }
+
+    fn as_slice(&self) -> &[&'r str] {
+        self
+    }
+}
+
+/// The type of a `#[form]` parameter, parsed from the values submitted for that field.
+///
+/// This is implemented for all types which implement [`FromFormValue`], for which a missing value is an error unless the type
+/// provides a [`from_missing_value`](FromFormValue::from_missing_value),
+/// for `Option<T>`, for which a missing or empty value is `None`,
+/// for `Vec<T>` and `heapless::Vec<T, N>`, which collect every submitted value, such as from a group of checkboxes,
+/// and for `Result<T, FieldError>`, which passes the error to the action rather than rejecting the submission.
+pub trait FormField<'r>: Sized {
+    /// The "type" attribute of the HTML input used to enter the field.
+    const INPUT_TYPE: &'static str;
+
+    /// The "step" attribute of the HTML input used to enter the field, unless overridden by `#[form(step = ...)]`.
+    const STEP: Option<&'static str>;
+
+    /// Whether the field may be submitted more than once, such as by a group of checkboxes or a `<select multiple>`.
+    const MULTIPLE: bool = false;
+
+    /// The `(value, label)` pairs of the options of a `<select>` or group of radio inputs, if the value must be one of them.
+    const OPTIONS: &'static [(&'static str, &'static str)];
+
+    /// The "maxlength" attribute of the HTML input used to enter the field, unless overridden by `#[form(max_length = ...)]`.
+    const MAX_LENGTH: Option<usize>;
+
+    /// The raw submitted values of the field.
+    type Values: FieldValues<'r>;
+
+    /// Parse the field from its submitted values.
+    fn from_form_field(values: &Self::Values) -> Result<Self, FieldError>;
+}
+
+impl<'r, T: FromFormValue<'r>> FormField<'r> for T {
+    const INPUT_TYPE: &'static str = T::INPUT_TYPE;
+    const STEP: Option<&'static str> = T::STEP;
+    const OPTIONS: &'static [(&'static str, &'static str)] = T::OPTIONS;
+    const MAX_LENGTH: Option<usize> = T::MAX_LENGTH;
+
+    type Values = Option<&'r str>;
+
+    fn from_form_field(values: &Self::Values) -> Result<Self, FieldError> {
+        match *values {
+            Some(value) => T::from_form_value(value),
+            None => T::from_missing_value(),
+        }
+    }
+}
+
+impl<'r, T: FromFormValue<'r>> FormField<'r> for Option<T> {
+    const INPUT_TYPE: &'static str = T::INPUT_TYPE;
+    const STEP: Option<&'static str> = T::STEP;
+    const OPTIONS: &'static [(&'static str, &'static str)] = T::OPTIONS;
+    const MAX_LENGTH: Option<usize> = T::MAX_LENGTH;
+
+    type Values = Option<&'r str>;
+
+    fn from_form_field(values: &Self::Values) -> Result<Self, FieldError> {
+        match *values {
+            None | Some("") => Ok(None),
+            Some(value) => T::from_form_value(value).map(Some),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'r, T: FromFormValue<'r>> FormField<'r> for alloc::vec::Vec<T> {
+    const INPUT_TYPE: &'static str = T::INPUT_TYPE;
+    const STEP: Option<&'static str> = T::STEP;
+    const MULTIPLE: bool = true;
+    const OPTIONS: &'static [(&'static str, &'static str)] = T::OPTIONS;
+    const MAX_LENGTH: Option<usize> = T::MAX_LENGTH;
+
+    type Values = alloc::vec::Vec<&'r str>;
+
+    fn from_form_field(values: &Self::Values) -> Result<Self, FieldError> {
+        values.iter().copied().map(T::from_form_value).collect()
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<'r, T: FromFormValue<'r>, const N: usize> FormField<'r> for heapless::Vec<T, N> {
+    const INPUT_TYPE: &'static str = T::INPUT_TYPE;
+    const STEP: Option<&'static str> = T::STEP;
+    const MULTIPLE: bool = true;
+    const OPTIONS: &'static [(&'static str, &'static str)] = T::OPTIONS;
+    const MAX_LENGTH: Option<usize> = T::MAX_LENGTH;
+
+    type Values = heapless::Vec<&'r str, N>;
+
+    fn from_form_field(values: &Self::Values) -> Result<Self, FieldError> {
+        values.iter().copied().map(T::from_form_value).collect()
+    }
+}
+
+impl<'r, T: FormField<'r>> FormField<'r> for Result<T, FieldError> {
+    const INPUT_TYPE: &'static str = T::INPUT_TYPE;
+    const STEP: Option<&'static str> = T::STEP;
+    const MULTIPLE: bool = T::MULTIPLE;
+    const OPTIONS: &'static [(&'static str, &'static str)] = T::OPTIONS;
+    const MAX_LENGTH: Option<usize> = T::MAX_LENGTH;
+
+    type Values = T::Values;
+
+    fn from_form_field(values: &Self::Values) -> Result<Self, FieldError> {
+        Ok(T::from_form_field(values))
+    }
+}
+
+/// A typed form field, which can be written as the values it would be submitted as,
+/// such as to submit an action with typed values.
+///
+/// This is implemented for all types which implement [`ToFormValue`], which are submitted as a single value,
+/// for `Option<T>`, for which `None` is not submitted,
+/// and for `Vec<T>` and `heapless::Vec<T, N>`, which submit every value.
+pub trait ToFormField {
+    /// Call `visit` with each value of the field, stopping if `visit` returns [`ControlFlow::Break`].
+    fn visit_form_values(
+        &self,
+        visit: &mut dyn FnMut(&dyn ToFormValue) -> ControlFlow<()>,
+    ) -> ControlFlow<()>;
+}
+
+impl<T: ToFormValue> ToFormField for T {
+    fn visit_form_values(
+        &self,
+        visit: &mut dyn FnMut(&dyn ToFormValue) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        visit(self)
+    }
+}
+
+impl<T: ToFormValue> ToFormField for Option<T> {
+    fn visit_form_values(
+        &self,
+        visit: &mut dyn FnMut(&dyn ToFormValue) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        match self {
+            Some(value) => visit(value),
+            None => ControlFlow::Continue(()),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ToFormValue> ToFormField for alloc::vec::Vec<T> {
+    fn visit_form_values(
+        &self,
+        visit: &mut dyn FnMut(&dyn ToFormValue) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        self.iter().try_for_each(|value| visit(value))
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<T: ToFormValue, const N: usize> ToFormField for heapless::Vec<T, N> {
+    fn visit_form_values(
+        &self,
+        visit: &mut dyn FnMut(&dyn ToFormValue) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        self.iter().try_for_each(|value| visit(value))
+    }
+}
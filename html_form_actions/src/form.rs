@@ -0,0 +1,84 @@
+use core::{fmt, ops::ControlFlow};
+
+use crate::{FieldError, FieldName, FormValue, NamePrefix, RejectedField};
+
+/// A struct whose fields are form fields, which can be used as a `#[form(flatten)]` parameter of several actions.
+///
+/// Implement this trait using `#[derive(HtmlForm)]`, which also generates a module named after the struct in `snake_case`,
+/// containing the `Form`, `FORM`, `Values`, `Errors`, `Prefill` and `Typed` items, as generated for each action,
+/// and `Names`, which builds the names of the fields when the struct is nested within another form.
+pub trait HtmlForm<'r>: Sized {
+    /// A description of the form fields, as used to render the HTML form.
+    type Form: 'static;
+
+    /// The description of the form fields.
+    const FORM: Self::Form;
+
+    /// The number of form fields, including the fields of flattened structs.
+    const FIELD_COUNT: usize;
+
+    /// Builds the names of the form fields, with a prefix if the form is nested.
+    type Names<'p>;
+
+    /// The raw submitted values of each field.
+    type Values: Default + Clone + fmt::Debug;
+
+    /// The reason each field was rejected, if it was.
+    type Errors: Default + Clone + fmt::Debug + Eq;
+
+    /// The values used to prefill the HTML inputs of each field.
+    type Prefill<'a>: Default + Clone + fmt::Debug;
+
+    /// The names of the form fields, with the given prefix.
+    fn names(prefix: NamePrefix<'_>) -> Self::Names<'_>;
+
+    /// Record the submitted `value` of the field with the given `name`, if it is one of the form fields.
+    fn push_value(values: &mut Self::Values, errors: &mut Self::Errors, name: &str, value: &'r str);
+
+    /// Parse each field from its submitted values, recording the reason each field was rejected into `errors`.
+    fn from_form(values: &Self::Values, errors: &mut Self::Errors) -> Option<Self>;
+
+    /// The reason the first rejected field was rejected, if any were.
+    fn first_error(errors: &Self::Errors) -> Option<FieldError>;
+
+    /// The values used to prefill the HTML inputs with the submitted `values`.
+    fn prefill(values: &Self::Values) -> Self::Prefill<'_>;
+
+    /// Call `visit` with the name and value of each submitted field, with the given prefix,
+    /// stopping if `visit` returns [`ControlFlow::Break`].
+    fn visit_pairs(
+        prefix: NamePrefix<'_>,
+        values: &Self::Values,
+        visit: &mut dyn FnMut(FieldName<'_>, FormValue<'_>) -> ControlFlow<()>,
+    ) -> ControlFlow<()>;
+
+    /// Render each field as HTML with the given prefix, prefilled with `values` and showing `errors`.
+    fn render_fields(
+        f: &mut fmt::Formatter<'_>,
+        prefix: NamePrefix<'_>,
+        values: &Self::Values,
+        errors: &Self::Errors,
+    ) -> fmt::Result;
+
+    /// The fields of a [`Rejection`](crate::Rejection) of the submitted `values`, with the given `errors`.
+    fn rejected_fields<'a>(
+        values: &'a Self::Values,
+        errors: &'a Self::Errors,
+    ) -> impl Iterator<Item = RejectedField<'a>>;
+}
+
+/// The typed values of the fields of a form, which can be written as the values they would be submitted as,
+/// such as to submit an action with typed values.
+///
+/// This is implemented by the `Typed` struct generated for each action and by `#[derive(HtmlForm)]`,
+/// whose fields may be any types which implement [`ToFormField`](crate::ToFormField),
+/// or [`ToHtmlForm`] and [`ToNestedForm`](crate::ToNestedForm) for `#[form(flatten)]` and `#[form(nested)]` fields.
+pub trait ToHtmlForm {
+    /// Call `visit` with the name and value of each field, with the given prefix,
+    /// stopping if `visit` returns [`ControlFlow::Break`].
+    fn visit_pairs(
+        &self,
+        prefix: NamePrefix<'_>,
+        visit: &mut dyn FnMut(FieldName<'_>, FormValue<'_>) -> ControlFlow<()>,
+    ) -> ControlFlow<()>;
+}
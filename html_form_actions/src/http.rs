@@ -0,0 +1,146 @@
+//! Integration with the [`http`](https://docs.rs/http) crate, for services built directly on hyper or on small custom routers.
+//!
+//! `#[actions(http)]` generates a handler which takes a [`Request`] with any `Unpin` [`Body`] and returns a [`Response`],
+//! using [`Http`] as its [`ActionBackend`].
+
+use alloc::{string::String, vec::Vec};
+use core::{marker::PhantomData, pin::Pin};
+
+use bytes::Buf;
+
+pub use ::http::{Request, Response, StatusCode};
+pub use http_body::Body;
+
+use crate::{ActionBackend, FromActionRequest, IntoActionResponse};
+
+/// The default limit of the length of the body of a request, in bytes.
+pub const DEFAULT_LIMIT: usize = 2 * 1024 * 1024;
+
+/// The [`ActionBackend`] of handlers generated by `#[actions(http)]`, which reads a request with a body of type `B`,
+/// which must be `Unpin`, as with hyper's `Incoming`.
+///
+/// Bodies longer than `LIMIT` bytes are rejected with "413 Payload Too Large".
+/// The limit may be declared with `#[actions(http(limit = 4096))]`, and is [`DEFAULT_LIMIT`] otherwise.
+///
+/// Actions may take the [`Method`](::http::Method), [`Uri`](::http::Uri), [`Version`](::http::Version)
+/// and [`HeaderMap`](::http::HeaderMap) of the request as parameters,
+/// and may return a `String`, a `&'static str`, a `(StatusCode, T)` pair or a `Response<String>`.
+pub struct Http<B, const LIMIT: usize = DEFAULT_LIMIT>(PhantomData<B>);
+
+/// A plain text response with the given `status`.
+fn text_response(status: StatusCode, body: String) -> Response<String> {
+    let mut response = Response::new(body);
+
+    *response.status_mut() = status;
+
+    response.headers_mut().insert(
+        ::http::header::CONTENT_TYPE,
+        ::http::HeaderValue::from_static("text/plain; charset=utf-8"),
+    );
+
+    response
+}
+
+/// Read the whole body, or respond with the reason it could not be read.
+async fn collect<B: Body + Unpin>(body: &mut B, limit: usize) -> Result<Vec<u8>, Response<String>> {
+    let too_large = || text_response(StatusCode::PAYLOAD_TOO_LARGE, "Payload Too Large".into());
+
+    if body.size_hint().lower() > limit as u64 {
+        return Err(too_large());
+    }
+
+    let mut body = Pin::new(body);
+
+    let mut bytes = Vec::new();
+
+    while let Some(frame) = core::future::poll_fn(|cx| body.as_mut().poll_frame(cx)).await {
+        let frame = frame
+            .map_err(|_| text_response(StatusCode::BAD_REQUEST, "Failed to read body".into()))?;
+
+        if let Ok(mut data) = frame.into_data() {
+            while data.has_remaining() {
+                let chunk = data.chunk();
+
+                if chunk.len() > limit - bytes.len() {
+                    return Err(too_large());
+                }
+
+                bytes.extend_from_slice(chunk);
+
+                let length = chunk.len();
+                data.advance(length);
+            }
+        }
+    }
+
+    Ok(bytes)
+}
+
+impl<B: Body + Unpin, const LIMIT: usize> ActionBackend for Http<B, LIMIT> {
+    type Request = Request<B>;
+    type Response = Response<String>;
+    type Body<'r> = Vec<u8>;
+
+    fn query(request: &Self::Request) -> Option<&str> {
+        request.uri().query()
+    }
+
+    async fn body(request: &mut Self::Request) -> Result<Self::Body<'_>, Self::Response> {
+        collect(request.body_mut(), LIMIT).await
+    }
+
+    fn not_found() -> Self::Response {
+        text_response(StatusCode::NOT_FOUND, "Action Not Found".into())
+    }
+
+    fn bad_request(reason: &'static str) -> Self::Response {
+        text_response(StatusCode::BAD_REQUEST, reason.into())
+    }
+}
+
+macro_rules! impl_from_request_parts {
+    ($($ty:ty => |$request:ident| $value:expr),* $(,)?) => {
+        $(
+            impl<B: Body + Unpin, const LIMIT: usize> FromActionRequest<Http<B, LIMIT>> for $ty {
+                async fn from_action_request($request: &mut Request<B>) -> Result<Self, Response<String>> {
+                    Ok($value)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_request_parts!(
+    ::http::Method => |request| request.method().clone(),
+    ::http::Uri => |request| request.uri().clone(),
+    ::http::Version => |request| request.version(),
+    ::http::HeaderMap => |request| request.headers().clone(),
+);
+
+impl<B: Body + Unpin, const LIMIT: usize> IntoActionResponse<Http<B, LIMIT>> for Response<String> {
+    fn into_action_response(self) -> Response<String> {
+        self
+    }
+}
+
+impl<B: Body + Unpin, const LIMIT: usize> IntoActionResponse<Http<B, LIMIT>> for String {
+    fn into_action_response(self) -> Response<String> {
+        text_response(StatusCode::OK, self)
+    }
+}
+
+impl<B: Body + Unpin, const LIMIT: usize> IntoActionResponse<Http<B, LIMIT>> for &'static str {
+    fn into_action_response(self) -> Response<String> {
+        text_response(StatusCode::OK, self.into())
+    }
+}
+
+impl<B: Body + Unpin, const LIMIT: usize, T: Into<String>> IntoActionResponse<Http<B, LIMIT>>
+    for (StatusCode, T)
+{
+    fn into_action_response(self) -> Response<String> {
+        let (status, body) = self;
+
+        text_response(status, body.into())
+    }
+}
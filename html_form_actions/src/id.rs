@@ -0,0 +1,68 @@
+use core::fmt;
+
+use crate::NamePrefix;
+
+/// The "id" attribute of a field, built from the id prefix of the action and the names of the parent fields if the field is nested,
+/// such as `page-update-items-3-qty`, so that structs deriving `HtmlForm` have distinct ids in each action and each element of a nested list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldId<'p> {
+    /// The prefix of the name of the field.
+    pub prefix: NamePrefix<'p>,
+    /// The id of the field within its form, such as `qty`.
+    pub id: &'static str,
+}
+
+impl fmt::Display for FieldId<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn write_prefix(f: &mut fmt::Formatter<'_>, prefix: &NamePrefix<'_>) -> fmt::Result {
+            match prefix {
+                NamePrefix::Root => Ok(()),
+                NamePrefix::Form { id } => write!(f, "{id}-"),
+                NamePrefix::Field { parent, name } => {
+                    write_prefix(f, parent)?;
+                    write!(f, "{name}-")
+                }
+                NamePrefix::Index {
+                    parent,
+                    name,
+                    index,
+                } => {
+                    write_prefix(f, parent)?;
+                    write!(f, "{name}-{index}-")
+                }
+            }
+        }
+
+        write_prefix(f, &self.prefix)?;
+
+        f.write_str(self.id)
+    }
+}
+
+/// The "id" attribute of an element of a form which is rendered several times on the same page,
+/// such as once for each row of a table, formatted as `{id}-{suffix}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SuffixedId<S> {
+    /// The id of the element within a single form, such as `value_id`.
+    pub id: &'static str,
+    /// The suffix which distinguishes this instance of the form, such as the index of the row.
+    pub suffix: S,
+}
+
+impl<S: fmt::Display> fmt::Display for SuffixedId<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.id, self.suffix)
+    }
+}
+
+/// A helper trait for making the ids of generated forms unique when a form is rendered several times on the same page.
+pub trait IdExt {
+    /// Append `suffix` to the id, such as `index_page-update_value-value-3`.
+    fn with_suffix<S: fmt::Display>(self, suffix: S) -> SuffixedId<S>;
+}
+
+impl IdExt for &'static str {
+    fn with_suffix<S: fmt::Display>(self, suffix: S) -> SuffixedId<S> {
+        SuffixedId { id: self, suffix }
+    }
+}
@@ -1,8 +1,45 @@
 #![cfg_attr(not(test), no_std)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(all(feature = "std", not(test)))]
+extern crate std;
+
+mod backend;
+mod constraints;
+#[cfg(feature = "embedded_io")]
+pub mod embedded_io;
+mod failure;
+mod field;
+mod form;
+#[cfg(feature = "http")]
+pub mod http;
+mod id;
+mod nested;
+mod options;
+mod prefill;
+mod render;
+mod submission;
+#[cfg(any(feature = "axum", feature = "testing-picoserve"))]
+pub mod testing;
 mod tests;
+pub mod urlencoded;
 
-pub use html_form_actions_macros::actions;
+pub use backend::{ActionBackend, FromActionRequest, IntoActionResponse};
+pub use constraints::Constraints;
+#[cfg(feature = "pattern")]
+pub use constraints::Pattern;
+pub use failure::{ActionResult, Failure, FieldError, RejectedField, Rejection};
+pub use field::{FieldValues, FormField, FromFormValue, ToFormField, ToFormValue};
+pub use form::{HtmlForm, ToHtmlForm};
+pub use html_form_actions_macros::{FormOptions, HtmlForm, actions};
+pub use id::{FieldId, IdExt, SuffixedId};
+pub use nested::{FieldName, IndexedNames, NamePrefix, NestedForm, ToNestedForm};
+pub use options::FormOptions;
+pub use prefill::FormValue;
+pub use render::{DefaultForm, FieldDescription, RenderedField};
+pub use submission::{FormAction, Submission};
 
 pub fn query_action(raw_query: Option<&str>) -> Option<&str> {
     raw_query?
@@ -0,0 +1,356 @@
+use core::{fmt, marker::PhantomData, ops::ControlFlow};
+
+use crate::{FieldError, FormValue, HtmlForm, ToHtmlForm};
+
+/// The prefix of the names of the fields of a nested form, such as `address.` or `items[3].`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NamePrefix<'p> {
+    /// The fields are not nested.
+    #[default]
+    Root,
+    /// The fields are the fields of a form whose ids are prefixed by `id`, such as `page-update` for the action `update` of the module `page`.
+    ///
+    /// The fields are not nested, so the names of the fields are not prefixed.
+    Form { id: &'static str },
+    /// The fields are nested within the field `name` of the parent form.
+    Field {
+        parent: &'p NamePrefix<'p>,
+        name: &'static str,
+    },
+    /// The fields are nested within the element `index` of the list field `name` of the parent form.
+    Index {
+        parent: &'p NamePrefix<'p>,
+        name: &'static str,
+        index: usize,
+    },
+}
+
+impl fmt::Display for NamePrefix<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Root | Self::Form { .. } => Ok(()),
+            Self::Field { parent, name } => write!(f, "{parent}{name}."),
+            Self::Index {
+                parent,
+                name,
+                index,
+            } => write!(f, "{parent}{name}[{index}]."),
+        }
+    }
+}
+
+/// The name of a form field which may be nested within other forms, such as `address.street` or `items[3].qty`,
+/// to be used as the "name" attribute of the HTML input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldName<'p> {
+    /// The prefix of the name.
+    pub prefix: NamePrefix<'p>,
+    /// The name of the field within its form.
+    pub name: &'static str,
+}
+
+impl fmt::Display for FieldName<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.prefix, self.name)
+    }
+}
+
+/// The type of a `#[form(nested)]` parameter, whose fields are submitted with a prefix.
+///
+/// This is implemented for all types which implement [`HtmlForm`], whose fields are named such as `address.street`,
+/// and for `Vec<T>` of such types, whose fields are named such as `items[3].qty`.
+/// Elements are ordered by their index, which need not start from zero or be contiguous, such as after a row is deleted by the page,
+/// and their fields may be submitted in any order.
+pub trait NestedForm<'r>: Sized {
+    /// Builds the names of the nested fields.
+    type Names<'p>;
+
+    /// The raw submitted values of the nested fields.
+    type Values: Default + Clone + fmt::Debug;
+
+    /// The reason each nested field was rejected, if it was.
+    type Errors: Default + Clone + fmt::Debug + Eq;
+
+    /// The values used to prefill the HTML inputs of the nested fields.
+    type Prefill<'a>: Default + Clone + fmt::Debug;
+
+    /// The names of the nested fields, nested within the field `name` of the parent form.
+    fn names<'p>(parent: &'p NamePrefix<'p>, name: &'static str) -> Self::Names<'p>;
+
+    /// Record the submitted `value` of the field with the given `name`, with the name of the parent field removed,
+    /// such as `.street` or `[3].qty`.
+    fn push_value(values: &mut Self::Values, errors: &mut Self::Errors, name: &str, value: &'r str);
+
+    /// Parse the nested fields from their submitted values, recording the reason each field was rejected into `errors`.
+    fn from_form(values: &Self::Values, errors: &mut Self::Errors) -> Option<Self>;
+
+    /// The reason the first rejected nested field was rejected, if any were.
+    fn first_error(errors: &Self::Errors) -> Option<FieldError>;
+
+    /// The values used to prefill the HTML inputs of the nested fields with the submitted `values`.
+    fn prefill(values: &Self::Values) -> Self::Prefill<'_>;
+
+    /// Call `visit` with the name and value of each submitted nested field, nested within the field `name` of the parent form,
+    /// stopping if `visit` returns [`ControlFlow::Break`].
+    fn visit_pairs(
+        parent: &NamePrefix<'_>,
+        name: &'static str,
+        values: &Self::Values,
+        visit: &mut dyn FnMut(FieldName<'_>, FormValue<'_>) -> ControlFlow<()>,
+    ) -> ControlFlow<()>;
+
+    /// Render the nested fields as HTML, nested within the field `name` of the parent form,
+    /// prefilled with `values` and showing `errors`.
+    fn render_fields(
+        f: &mut fmt::Formatter<'_>,
+        parent: &NamePrefix<'_>,
+        name: &'static str,
+        values: &Self::Values,
+        errors: &Self::Errors,
+    ) -> fmt::Result;
+}
+
+impl<'r, T: HtmlForm<'r>> NestedForm<'r> for T {
+    type Names<'p> = T::Names<'p>;
+    type Values = T::Values;
+    type Errors = T::Errors;
+    type Prefill<'a> = T::Prefill<'a>;
+
+    fn names<'p>(parent: &'p NamePrefix<'p>, name: &'static str) -> Self::Names<'p> {
+        T::names(NamePrefix::Field { parent, name })
+    }
+
+    fn push_value(
+        values: &mut Self::Values,
+        errors: &mut Self::Errors,
+        name: &str,
+        value: &'r str,
+    ) {
+        if let Some(name) = name.strip_prefix('.') {
+            T::push_value(values, errors, name, value);
+        }
+    }
+
+    fn from_form(values: &Self::Values, errors: &mut Self::Errors) -> Option<Self> {
+        T::from_form(values, errors)
+    }
+
+    fn first_error(errors: &Self::Errors) -> Option<FieldError> {
+        T::first_error(errors)
+    }
+
+    fn prefill(values: &Self::Values) -> Self::Prefill<'_> {
+        T::prefill(values)
+    }
+
+    fn visit_pairs(
+        parent: &NamePrefix<'_>,
+        name: &'static str,
+        values: &Self::Values,
+        visit: &mut dyn FnMut(FieldName<'_>, FormValue<'_>) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        T::visit_pairs(NamePrefix::Field { parent, name }, values, visit)
+    }
+
+    fn render_fields(
+        f: &mut fmt::Formatter<'_>,
+        parent: &NamePrefix<'_>,
+        name: &'static str,
+        values: &Self::Values,
+        errors: &Self::Errors,
+    ) -> fmt::Result {
+        f.write_str("<fieldset>")?;
+        T::render_fields(f, NamePrefix::Field { parent, name }, values, errors)?;
+        f.write_str("</fieldset>")
+    }
+}
+
+/// Builds the names of the fields of each element of a `#[form(nested)]` list.
+pub struct IndexedNames<'p, T> {
+    parent: &'p NamePrefix<'p>,
+    name: &'static str,
+    _form: PhantomData<fn() -> T>,
+}
+
+impl<'p, T: HtmlForm<'static>> IndexedNames<'p, T> {
+    /// The names of the fields of the element `index`.
+    pub fn at(&self, index: usize) -> T::Names<'p> {
+        T::names(NamePrefix::Index {
+            parent: self.parent,
+            name: self.name,
+            index,
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'r, T: HtmlForm<'r>> NestedForm<'r> for alloc::vec::Vec<T> {
+    type Names<'p> = IndexedNames<'p, T>;
+    /// The index and values of each submitted element, ordered by index.
+    type Values = alloc::vec::Vec<(usize, T::Values)>;
+    type Errors = alloc::vec::Vec<T::Errors>;
+    type Prefill<'a> = alloc::vec::Vec<T::Prefill<'a>>;
+
+    fn names<'p>(parent: &'p NamePrefix<'p>, name: &'static str) -> Self::Names<'p> {
+        IndexedNames {
+            parent,
+            name,
+            _form: PhantomData,
+        }
+    }
+
+    fn push_value(
+        values: &mut Self::Values,
+        errors: &mut Self::Errors,
+        name: &str,
+        value: &'r str,
+    ) {
+        let Some((index, name)) = name
+            .strip_prefix('[')
+            .and_then(|name| name.split_once("]."))
+        else {
+            return;
+        };
+
+        let Ok(index) = index.parse::<usize>() else {
+            return;
+        };
+
+        // Elements are only added for submitted values, so that a large index cannot allocate a large number of elements
+        let position = match values.binary_search_by_key(&index, |&(index, _)| index) {
+            Ok(position) => position,
+            Err(position) => {
+                values.insert(position, (index, T::Values::default()));
+                errors.insert(position, T::Errors::default());
+
+                position
+            }
+        };
+
+        if let (Some((_, values)), Some(errors)) =
+            (values.get_mut(position), errors.get_mut(position))
+        {
+            T::push_value(values, errors, name, value);
+        }
+    }
+
+    fn from_form(values: &Self::Values, errors: &mut Self::Errors) -> Option<Self> {
+        let mut items = alloc::vec::Vec::with_capacity(values.len());
+        let mut is_valid = true;
+
+        for ((_, values), errors) in values.iter().zip(errors) {
+            match T::from_form(values, errors) {
+                Some(item) => items.push(item),
+                None => is_valid = false,
+            }
+        }
+
+        is_valid.then_some(items)
+    }
+
+    fn first_error(errors: &Self::Errors) -> Option<FieldError> {
+        errors.iter().find_map(T::first_error)
+    }
+
+    fn prefill(values: &Self::Values) -> Self::Prefill<'_> {
+        values
+            .iter()
+            .map(|(_, values)| T::prefill(values))
+            .collect()
+    }
+
+    fn visit_pairs(
+        parent: &NamePrefix<'_>,
+        name: &'static str,
+        values: &Self::Values,
+        visit: &mut dyn FnMut(FieldName<'_>, FormValue<'_>) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        for &(index, ref values) in values {
+            let prefix = NamePrefix::Index {
+                parent,
+                name,
+                index,
+            };
+
+            T::visit_pairs(prefix, values, visit)?;
+        }
+
+        ControlFlow::Continue(())
+    }
+
+    /// Each element of `values` is rendered with its submitted index, so a list without submitted values renders no fields.
+    fn render_fields(
+        f: &mut fmt::Formatter<'_>,
+        parent: &NamePrefix<'_>,
+        name: &'static str,
+        values: &Self::Values,
+        errors: &Self::Errors,
+    ) -> fmt::Result {
+        for (position, &(index, ref values)) in values.iter().enumerate() {
+            let prefix = NamePrefix::Index {
+                parent,
+                name,
+                index,
+            };
+
+            f.write_str("<fieldset>")?;
+            T::render_fields(
+                f,
+                prefix,
+                values,
+                &errors.get(position).cloned().unwrap_or_default(),
+            )?;
+            f.write_str("</fieldset>")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The typed values of a `#[form(nested)]` field, which can be written as the values they would be submitted as.
+///
+/// This is implemented for all types which implement [`ToHtmlForm`],
+/// and for `Vec<T>` of such types, whose elements are submitted with their position as their index.
+pub trait ToNestedForm {
+    /// Call `visit` with the name and value of each nested field, nested within the field `name` of the parent form,
+    /// stopping if `visit` returns [`ControlFlow::Break`].
+    fn visit_pairs(
+        &self,
+        parent: &NamePrefix<'_>,
+        name: &'static str,
+        visit: &mut dyn FnMut(FieldName<'_>, FormValue<'_>) -> ControlFlow<()>,
+    ) -> ControlFlow<()>;
+}
+
+impl<T: ToHtmlForm> ToNestedForm for T {
+    fn visit_pairs(
+        &self,
+        parent: &NamePrefix<'_>,
+        name: &'static str,
+        visit: &mut dyn FnMut(FieldName<'_>, FormValue<'_>) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        T::visit_pairs(self, NamePrefix::Field { parent, name }, visit)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: ToHtmlForm> ToNestedForm for alloc::vec::Vec<T> {
+    fn visit_pairs(
+        &self,
+        parent: &NamePrefix<'_>,
+        name: &'static str,
+        visit: &mut dyn FnMut(FieldName<'_>, FormValue<'_>) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        for (index, element) in self.iter().enumerate() {
+            let prefix = NamePrefix::Index {
+                parent,
+                name,
+                index,
+            };
+
+            element.visit_pairs(prefix, visit)?;
+        }
+
+        ControlFlow::Continue(())
+    }
+}
@@ -0,0 +1,15 @@
+/// A fieldless enum whose variants are the options of a `<select>` or a group of radio inputs,
+/// implemented using `#[derive(FormOptions)]`.
+///
+/// The derive also implements [`FromFormValue`](crate::FromFormValue), which accepts exactly the values of [`OPTIONS`](Self::OPTIONS),
+/// so the rendered options and the parser cannot drift apart.
+pub trait FormOptions: Sized + 'static {
+    /// The value and label of each variant, in declaration order.
+    const OPTIONS: &'static [(&'static str, &'static str)];
+
+    /// The value of the variant, to be used as the "value" attribute of the HTML option or input.
+    fn value(&self) -> &'static str;
+
+    /// The label of the variant, to be shown to the user.
+    fn label(&self) -> &'static str;
+}
@@ -0,0 +1,58 @@
+use core::fmt;
+
+use crate::{ToFormValue, render::Escaped};
+
+/// The value used to prefill an HTML input, which is either a raw submitted value,
+/// such as when re-rendering a rejected submission, or a typed value, such as the current value on an edit page.
+///
+/// The [`Display`](fmt::Display) implementation writes the value as it would be submitted,
+/// to be used as the "value" attribute of the HTML input.
+#[derive(Clone, Copy)]
+pub enum FormValue<'a> {
+    /// A raw submitted value.
+    Raw(&'a str),
+    /// A typed value.
+    Typed(&'a dyn ToFormValue),
+}
+
+impl<'a> FormValue<'a> {
+    /// The "value" attribute of the HTML input, with a leading space, such as ` value="3"`, escaped for use in HTML.
+    pub fn value_attribute(self) -> impl fmt::Display + 'a {
+        struct ValueAttribute<'a>(FormValue<'a>);
+
+        impl fmt::Display for ValueAttribute<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, r#" value="{}""#, Escaped(self.0))
+            }
+        }
+
+        ValueAttribute(self)
+    }
+}
+
+impl<'a, T: ToFormValue> From<&'a T> for FormValue<'a> {
+    fn from(value: &'a T) -> Self {
+        Self::Typed(value)
+    }
+}
+
+impl fmt::Debug for FormValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Raw(value) => f.debug_tuple("Raw").field(value).finish(),
+            Self::Typed(_) => f
+                .debug_tuple("Typed")
+                .field(&format_args!("{self}"))
+                .finish(),
+        }
+    }
+}
+
+impl fmt::Display for FormValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Raw(value) => f.write_str(value),
+            Self::Typed(value) => value.fmt_form_value(f),
+        }
+    }
+}
@@ -0,0 +1,313 @@
+use core::fmt::{self, Write};
+
+use crate::{FieldError, FieldId, FieldName, NamePrefix};
+
+/// Escapes text written to the inner formatter for use in HTML content and attribute values.
+struct EscapingWriter<'a, 'f>(&'a mut fmt::Formatter<'f>);
+
+impl fmt::Write for EscapingWriter<'_, '_> {
+    fn write_str(&mut self, mut text: &str) -> fmt::Result {
+        while let Some(index) = text.find(['&', '<', '>', '"', '\'']) {
+            self.0.write_str(&text[..index])?;
+
+            self.0.write_str(match text.as_bytes()[index] {
+                b'&' => "&amp;",
+                b'<' => "&lt;",
+                b'>' => "&gt;",
+                b'"' => "&quot;",
+                _ => "&#39;",
+            })?;
+
+            text = &text[(index + 1)..];
+        }
+
+        self.0.write_str(text)
+    }
+}
+
+/// A value escaped for use in HTML content and attribute values.
+pub(crate) struct Escaped<T>(pub(crate) T);
+
+impl<T: fmt::Display> fmt::Display for Escaped<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(EscapingWriter(f), "{}", self.0)
+    }
+}
+
+/// The description of a single `#[form]` field, as used to render a default form.
+///
+/// The fields have the same values as the corresponding fields of the generated `Form`,
+/// except for `id` and `error_id`, which are the ids within the form, such as `value` and `value-error`,
+/// and are prefixed by the id prefix of the action and the names of any parent fields when rendered.
+#[derive(Debug, Clone, Copy)]
+pub struct FieldDescription {
+    pub name: &'static str,
+    pub id: &'static str,
+    pub error_id: &'static str,
+    pub label: &'static str,
+    pub placeholder: Option<&'static str>,
+    pub help: Option<&'static str>,
+    pub input_type: &'static str,
+    pub multiple: bool,
+    pub options: &'static [(&'static str, &'static str)],
+    pub required: bool,
+    pub min: Option<&'static str>,
+    pub max: Option<&'static str>,
+    pub step: Option<&'static str>,
+    pub min_length: Option<usize>,
+    pub max_length: Option<usize>,
+    pub pattern: Option<&'static str>,
+}
+
+impl FieldDescription {
+    /// Render the field as HTML, with its label, help text and error message,
+    /// prefilled with the submitted `values` and nested within the given `prefix`.
+    pub fn render<'a>(
+        &'a self,
+        prefix: NamePrefix<'a>,
+        values: &'a [&'a str],
+        error: Option<FieldError>,
+    ) -> RenderedField<'a> {
+        RenderedField {
+            description: self,
+            prefix,
+            values,
+            error,
+        }
+    }
+}
+
+/// A single field rendered as HTML, created by [`FieldDescription::render`].
+pub struct RenderedField<'a> {
+    description: &'a FieldDescription,
+    prefix: NamePrefix<'a>,
+    values: &'a [&'a str],
+    error: Option<FieldError>,
+}
+
+impl RenderedField<'_> {
+    /// Write the "name", "type" and constraint attributes of the input, and "value" if `value` is given.
+    fn write_input(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        input_type: &str,
+        value: Option<&str>,
+    ) -> fmt::Result {
+        let FieldDescription {
+            name,
+            required,
+            min,
+            max,
+            step,
+            min_length,
+            max_length,
+            pattern,
+            placeholder,
+            ..
+        } = *self.description;
+
+        let name = FieldName {
+            prefix: self.prefix,
+            name,
+        };
+
+        write!(
+            f,
+            r#"<input type="{}" name="{}""#,
+            Escaped(input_type),
+            Escaped(name)
+        )?;
+
+        if let Some(value) = value {
+            write!(f, r#" value="{}""#, Escaped(value))?;
+        }
+
+        if required {
+            f.write_str(" required")?;
+        }
+
+        for (attribute, value) in [
+            ("min", min),
+            ("max", max),
+            ("step", step),
+            ("pattern", pattern),
+            ("placeholder", placeholder),
+        ] {
+            if let Some(value) = value {
+                write!(f, r#" {attribute}="{}""#, Escaped(value))?;
+            }
+        }
+
+        for (attribute, value) in [("minlength", min_length), ("maxlength", max_length)] {
+            if let Some(value) = value {
+                write!(f, r#" {attribute}="{value}""#)?;
+            }
+        }
+
+        self.write_described_by(f)
+    }
+
+    fn write_described_by(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.error.is_some() {
+            write!(
+                f,
+                r#" aria-invalid="true" aria-describedby="{}""#,
+                Escaped(self.id(self.description.error_id))
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn id(&self, id: &'static str) -> FieldId<'_> {
+        FieldId {
+            prefix: self.prefix,
+            id,
+        }
+    }
+
+    fn is_checked(&self, value: &str) -> bool {
+        self.values.contains(&value)
+    }
+}
+
+impl fmt::Display for RenderedField<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let FieldDescription {
+            id,
+            error_id,
+            label,
+            help,
+            input_type,
+            multiple,
+            options,
+            ..
+        } = *self.description;
+
+        if input_type == "hidden" {
+            self.write_input(f, input_type, self.values.first().copied())?;
+
+            return f.write_str(">");
+        }
+
+        f.write_str("<div>")?;
+
+        if !options.is_empty() {
+            // Each option has its own input, so the label describes the group of inputs
+            write!(f, "<fieldset><legend>{}</legend>", Escaped(label))?;
+
+            let input_type = if multiple { "checkbox" } else { input_type };
+
+            for &(value, label) in options {
+                f.write_str("<label>")?;
+                self.write_input(f, input_type, Some(value))?;
+
+                if self.is_checked(value) {
+                    f.write_str(" checked")?;
+                }
+
+                write!(f, ">{}</label>", Escaped(label))?;
+            }
+
+            f.write_str("</fieldset>")?;
+        } else if input_type == "checkbox" {
+            write!(
+                f,
+                r#"<label for="{}">{}</label>"#,
+                Escaped(self.id(id)),
+                Escaped(label)
+            )?;
+            self.write_input(f, input_type, None)?;
+            write!(f, r#" id="{}""#, Escaped(self.id(id)))?;
+
+            if self.is_checked("on") || self.is_checked("true") {
+                f.write_str(" checked")?;
+            }
+
+            f.write_str(">")?;
+        } else {
+            write!(
+                f,
+                r#"<label for="{}">{}</label>"#,
+                Escaped(self.id(id)),
+                Escaped(label)
+            )?;
+
+            self.write_input(f, input_type, self.values.first().copied())?;
+            write!(f, r#" id="{}">"#, Escaped(self.id(id)))?;
+
+            // Multi-valued fields have an additional input for each other submitted value
+            if multiple {
+                for &value in self.values.iter().skip(1) {
+                    self.write_input(f, input_type, Some(value))?;
+                    f.write_str(">")?;
+                }
+            }
+        }
+
+        if let Some(help) = help {
+            write!(f, "<small>{}</small>", Escaped(help))?;
+        }
+
+        if let Some(error) = self.error {
+            write!(
+                f,
+                r#"<p id="{}">{}</p>"#,
+                Escaped(self.id(error_id)),
+                Escaped(error)
+            )?;
+        }
+
+        f.write_str("</div>")
+    }
+}
+
+/// A complete HTML form for an action, rendered from the description of its `#[form]` parameters,
+/// with labelled inputs prefilled with `values`, the message of each error in `errors`, and a submit button.
+///
+/// Each action module contains a `render` function which creates this form.
+/// It implements [`Display`](fmt::Display), and [`maud::Render`] if the `maud` feature is enabled.
+pub struct DefaultForm<'a, V, E> {
+    /// The action, as used in the "action" attribute of the HTML form.
+    pub action: &'static str,
+    /// The prefix of the ids of the fields, such as `page-my_action`.
+    pub id_prefix: &'static str,
+    /// The text of the submit button.
+    pub submit: &'static str,
+    /// The raw submitted values, which are used to prefill the inputs.
+    pub values: &'a V,
+    /// The reason each field was rejected, which is shown alongside the input.
+    pub errors: &'a E,
+    /// Render the fields of the form.
+    pub render_fields: fn(&mut fmt::Formatter<'_>, NamePrefix<'_>, &V, &E) -> fmt::Result,
+}
+
+impl<V, E> fmt::Display for DefaultForm<'_, V, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            r#"<form method="post" action="{}">"#,
+            Escaped(self.action)
+        )?;
+
+        (self.render_fields)(
+            f,
+            NamePrefix::Form { id: self.id_prefix },
+            self.values,
+            self.errors,
+        )?;
+
+        write!(
+            f,
+            r#"<button type="submit">{}</button></form>"#,
+            Escaped(self.submit)
+        )
+    }
+}
+
+#[cfg(feature = "maud")]
+impl<V, E> maud::Render for DefaultForm<'_, V, E> {
+    fn render_to(&self, buffer: &mut alloc::string::String) {
+        let _ = write!(buffer, "{self}");
+    }
+}
@@ -0,0 +1,107 @@
+use core::fmt;
+
+use core::ops::ControlFlow;
+
+use crate::{
+    FieldName, FormValue, NamePrefix, ToHtmlForm,
+    urlencoded::{UrlEncoded, VisitPairs},
+};
+
+/// An action, used to write code which is generic over actions, such as test clients.
+///
+/// Each action module contains a type called `Action` which implements this trait.
+pub trait FormAction {
+    /// The action, as used in the "action" attribute of the HTML form.
+    const ACTION: &'static str;
+
+    /// The raw submitted values of the action's `#[form]` parameters.
+    type Values<'r>: Default + Clone + fmt::Debug;
+
+    /// A submission of the action with the given `values`.
+    fn submission<'a, 'r>(values: &'a Self::Values<'r>) -> Submission<'a, Self::Values<'r>>;
+}
+
+/// A submission of an action, which can be sent to the page of its module to run the action,
+/// such as from tests, command line tools or other servers.
+///
+/// Each action module contains a `submission` function which creates a submission of the action's raw `Values`,
+/// and a `typed_submission` function which creates a submission of the action's `Typed` values.
+/// If the `serde` feature is enabled, the submission implements [`serde::Serialize`] as a sequence of name-value pairs,
+/// which is accepted by `application/x-www-form-urlencoded` serializers such as `serde_urlencoded`.
+pub struct Submission<'a, V> {
+    /// The action, as used in the "action" attribute of the HTML form.
+    pub action: &'static str,
+    /// The submitted values, which are either raw `Values` or `Typed` values.
+    pub values: &'a V,
+    /// Visit the name and value of each submitted field.
+    pub visit_pairs: VisitPairs<V>,
+}
+
+impl<'a, V: ToHtmlForm> Submission<'a, V> {
+    /// A submission of the typed `values` to `action`.
+    pub fn typed(action: &'static str, values: &'a V) -> Self {
+        fn visit_pairs<V: ToHtmlForm>(
+            prefix: NamePrefix<'_>,
+            values: &V,
+            visit: &mut dyn FnMut(FieldName<'_>, FormValue<'_>) -> ControlFlow<()>,
+        ) -> ControlFlow<()> {
+            values.visit_pairs(prefix, visit)
+        }
+
+        Self {
+            action,
+            values,
+            visit_pairs,
+        }
+    }
+}
+
+impl<'a, V> Submission<'a, V> {
+    /// The request target, such as `?/my_action`, relative to the path of the page.
+    pub fn target(&self) -> &'static str {
+        self.action
+    }
+
+    /// The request body, encoded as `application/x-www-form-urlencoded`.
+    pub fn body(&self) -> UrlEncoded<'a, V> {
+        UrlEncoded {
+            values: self.values,
+            visit_pairs: self.visit_pairs,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<V> serde::Serialize for Submission<'_, V> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeSeq;
+
+        /// Serializes a name or value as a string.
+        struct Text<T>(T);
+
+        impl<T: fmt::Display> serde::Serialize for Text<T> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(&self.0)
+            }
+        }
+
+        let mut pairs = serializer.serialize_seq(None)?;
+        let mut result = Ok(());
+
+        let _ = (self.visit_pairs)(
+            NamePrefix::Root,
+            self.values,
+            &mut |name, value| match pairs.serialize_element(&(Text(name), Text(value))) {
+                Ok(()) => ControlFlow::Continue(()),
+                Err(error) => {
+                    result = Err(error);
+                    ControlFlow::Break(())
+                }
+            },
+        );
+
+        result?;
+
+        pairs.end()
+    }
+}
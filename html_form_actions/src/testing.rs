@@ -0,0 +1,7 @@
+//! Clients for testing actions in-process, without a network.
+
+#[cfg(feature = "axum")]
+pub mod axum;
+
+#[cfg(feature = "testing-picoserve")]
+pub mod picoserve;
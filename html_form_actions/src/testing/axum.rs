@@ -0,0 +1,87 @@
+//! An in-process test client for routers which use the handlers generated by `#[actions(axum)]`.
+
+use alloc::string::String;
+
+use axum::http::{HeaderMap, HeaderValue, Method, Request, StatusCode, header::CONTENT_TYPE};
+use tower::ServiceExt;
+
+use crate::{FormAction, Submission, ToHtmlForm};
+
+/// The response to a submission sent by a [`Client`].
+#[derive(Debug, Clone)]
+pub struct TestResponse {
+    /// The status of the response.
+    pub status: StatusCode,
+    /// The headers of the response.
+    pub headers: HeaderMap,
+    /// The body of the response, which is assumed to be UTF-8.
+    pub body: String,
+}
+
+/// Sends submissions of actions to an [`axum::Router`], without a network.
+#[derive(Debug, Clone)]
+pub struct Client {
+    router: axum::Router,
+    path: String,
+}
+
+impl Client {
+    /// A client which sends submissions to the page at `path` of `router`.
+    pub fn new(router: axum::Router, path: impl Into<String>) -> Self {
+        Self {
+            router,
+            path: path.into(),
+        }
+    }
+
+    /// Submit typed values, such as `client.submit(page::my_action::typed_submission(&page::my_action::Typed { a: 1 }))`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the request cannot be built, or if the body of the response cannot be read or is not UTF-8.
+    pub async fn submit<V: ToHtmlForm>(&self, submission: Submission<'_, V>) -> TestResponse {
+        self.send(submission).await
+    }
+
+    /// Submit the raw `values` of the action `A`, such as `client.submit_raw::<page::my_action::Action>(values)`,
+    /// which may be values that the typed values can't represent, to test how invalid submissions are handled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the request cannot be built, or if the body of the response cannot be read or is not UTF-8.
+    pub async fn submit_raw<A: FormAction>(&self, values: A::Values<'_>) -> TestResponse {
+        self.send(A::submission(&values)).await
+    }
+
+    async fn send<V>(&self, submission: Submission<'_, V>) -> TestResponse {
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri(alloc::format!("{}{}", self.path, submission.target()))
+            .header(
+                CONTENT_TYPE,
+                HeaderValue::from_static("application/x-www-form-urlencoded"),
+            )
+            .body(axum::body::Body::from(alloc::format!(
+                "{}",
+                submission.body()
+            )))
+            .expect("the request is valid");
+
+        let response = match self.router.clone().oneshot(request).await {
+            Ok(response) => response,
+            Err(infallible) => match infallible {},
+        };
+
+        let (parts, body) = response.into_parts();
+
+        let body = axum::body::to_bytes(body, usize::MAX)
+            .await
+            .expect("the body can be read");
+
+        TestResponse {
+            status: parts.status,
+            headers: parts.headers,
+            body: String::from_utf8(body.into()).expect("the body is UTF-8"),
+        }
+    }
+}
@@ -0,0 +1,230 @@
+//! An in-process test client for routers which use the handlers generated by `#[actions(picoserve)]`.
+//!
+//! The requests are served by [`picoserve::serve_with_state`] over an in-memory connection, without a socket or a runtime,
+//! so the client can be used with any executor, such as `#[tokio::test]`.
+//! That function takes a socket only if `picoserve` is built without its `tokio` and `embassy` features,
+//! so neither may be enabled in the same build as this client.
+
+use alloc::{string::String, vec::Vec};
+use core::{convert::Infallible, time::Duration};
+
+use picoserve::{
+    Config, Timeouts, Timer,
+    io::{ErrorType, Socket, Write},
+    routing::PathRouter,
+};
+
+use crate::{FormAction, Submission, ToHtmlForm};
+
+/// The response to a submission sent by a [`Client`].
+#[derive(Debug, Clone)]
+pub struct TestResponse {
+    /// The status code of the response.
+    pub status: u16,
+    /// The headers of the response, in the order they were written.
+    pub headers: Vec<(String, String)>,
+    /// The body of the response, which is assumed to be UTF-8.
+    pub body: String,
+}
+
+impl TestResponse {
+    /// The value of the first header called `name`, which is compared case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Parse a raw HTTP response.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the response is malformed or not UTF-8.
+    fn parse(response: &[u8]) -> Self {
+        let response = core::str::from_utf8(response).expect("the response is UTF-8");
+
+        let (head, body) = response
+            .split_once("\r\n\r\n")
+            .expect("the response has a head");
+
+        let mut lines = head.split("\r\n");
+
+        let status = lines
+            .next()
+            .and_then(|status_line| status_line.split(' ').nth(1))
+            .and_then(|status| status.parse().ok())
+            .expect("the response has a status line");
+
+        let headers = lines
+            .map(|line| {
+                let (name, value) = line.split_once(':').expect("the header is valid");
+
+                (name.into(), value.trim().into())
+            })
+            .collect();
+
+        Self {
+            status,
+            headers,
+            body: body.into(),
+        }
+    }
+}
+
+/// Sends submissions of actions to a [`picoserve::Router`], without a network.
+pub struct Client<'a, P: PathRouter<State>, State = ()> {
+    app: &'a picoserve::Router<P, State>,
+    state: &'a State,
+    path: &'a str,
+}
+
+impl<'a, P: PathRouter> Client<'a, P> {
+    /// A client which sends submissions to the page at `path` of `app`, which has no state.
+    pub fn new(app: &'a picoserve::Router<P>, path: &'a str) -> Self {
+        Self::with_state(app, &(), path)
+    }
+}
+
+impl<'a, P: PathRouter<State>, State> Client<'a, P, State> {
+    /// A client which sends submissions to the page at `path` of `app`, which has a state of `state`.
+    pub fn with_state(
+        app: &'a picoserve::Router<P, State>,
+        state: &'a State,
+        path: &'a str,
+    ) -> Self {
+        Self { app, state, path }
+    }
+
+    /// Submit typed values, such as `client.submit(page::my_action::typed_submission(&page::my_action::Typed { a: 1 }))`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the request cannot be served, or if the response is malformed or not UTF-8.
+    pub async fn submit<V: ToHtmlForm>(&self, submission: Submission<'_, V>) -> TestResponse {
+        self.send_submission(submission).await
+    }
+
+    /// Submit the raw `values` of the action `A`, such as `client.submit_raw::<page::my_action::Action>(values)`,
+    /// which may be values that the typed values can't represent, to test how invalid submissions are handled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the request cannot be served, or if the response is malformed or not UTF-8.
+    pub async fn submit_raw<A: FormAction>(&self, values: A::Values<'_>) -> TestResponse {
+        self.send_submission(A::submission(&values)).await
+    }
+
+    async fn send_submission<V>(&self, submission: Submission<'_, V>) -> TestResponse {
+        let body = alloc::format!("{}", submission.body());
+
+        let request = alloc::format!(
+            "POST {}{} HTTP/1.1\r\n\
+            Content-Type: application/x-www-form-urlencoded\r\n\
+            Content-Length: {}\r\n\
+            Connection: close\r\n\
+            \r\n\
+            {body}",
+            self.path,
+            submission.target(),
+            body.len(),
+        );
+
+        TestResponse::parse(&self.send(request.as_bytes()).await)
+    }
+
+    /// Serve the raw HTTP `request`, returning the raw HTTP response.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the request cannot be served.
+    pub async fn send(&self, request: &[u8]) -> Vec<u8> {
+        let config = Config::new(Timeouts {
+            start_read_request: None,
+            persistent_start_read_request: None,
+            read_request: None,
+            write: None,
+        })
+        .close_connection_after_response();
+
+        let mut buffer = [0; 2048];
+        let mut response = Vec::new();
+
+        picoserve::serve_with_state(
+            self.app,
+            NoTimeouts,
+            &config,
+            &mut buffer,
+            Connection {
+                request,
+                response: &mut response,
+            },
+            self.state,
+        )
+        .await
+        .expect("the request is served");
+
+        response
+    }
+}
+
+/// A timer for a connection without timeouts, as an in-memory connection never waits.
+struct NoTimeouts;
+
+impl Timer for NoTimeouts {
+    type Duration = Duration;
+    type TimeoutError = Infallible;
+
+    async fn run_with_timeout<F: Future>(
+        &mut self,
+        _duration: Duration,
+        future: F,
+    ) -> Result<F::Output, Infallible> {
+        Ok(future.await)
+    }
+}
+
+/// An in-memory connection, which reads `request` and records the response into `response`.
+struct Connection<'a> {
+    request: &'a [u8],
+    response: &'a mut Vec<u8>,
+}
+
+/// Records the bytes written to the connection.
+struct ResponseWriter<'a>(&'a mut Vec<u8>);
+
+impl ErrorType for ResponseWriter<'_> {
+    type Error = Infallible;
+}
+
+impl Write for ResponseWriter<'_> {
+    async fn write(&mut self, buf: &[u8]) -> Result<usize, Infallible> {
+        self.0.extend_from_slice(buf);
+
+        Ok(buf.len())
+    }
+}
+
+impl Socket for Connection<'_> {
+    type Error = Infallible;
+    type ReadHalf<'b>
+        = &'b [u8]
+    where
+        Self: 'b;
+    type WriteHalf<'b>
+        = ResponseWriter<'b>
+    where
+        Self: 'b;
+
+    fn split(&mut self) -> (Self::ReadHalf<'_>, Self::WriteHalf<'_>) {
+        (self.request, ResponseWriter(self.response))
+    }
+
+    async fn shutdown<T: Timer>(
+        self,
+        _timeouts: &Timeouts<T::Duration>,
+        _timer: &mut T,
+    ) -> Result<(), picoserve::Error<Infallible>> {
+        Ok(())
+    }
+}
@@ -1,4 +1,20 @@
 #![cfg(test)]
 
+mod actix_web;
+// Some tests only build a service from their router, to check that the generated handler is accepted by axum
+#[allow(unused_must_use)]
 mod axum;
+mod backend;
+mod constraints;
+mod embedded_io;
+mod field;
+mod http;
+mod nested;
+mod parse;
 mod picoserve;
+mod poem;
+mod prefill;
+mod render;
+mod rocket;
+mod salvo;
+mod urlencoded;
@@ -0,0 +1,196 @@
+use actix_web::{App, http::StatusCode, test};
+
+use crate::actions;
+
+async fn post(
+    configure: fn(&mut actix_web::web::ServiceConfig),
+    uri: &str,
+    body: &'static str,
+) -> (StatusCode, String) {
+    let service = test::init_service(App::new().configure(configure)).await;
+
+    let response = test::call_service(
+        &service,
+        test::TestRequest::post()
+            .uri(uri)
+            .insert_header(("Content-Type", "application/x-www-form-urlencoded"))
+            .set_payload(body)
+            .to_request(),
+    )
+    .await;
+
+    let status = response.status();
+    let body = test::read_body(response).await;
+
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[actix_web::test]
+async fn basic() {
+    #[actions(actix_web)]
+    mod page {
+        use actix_web::web;
+
+        use crate as html_form_actions;
+
+        pub struct AppState {
+            pub offset: i32,
+        }
+
+        pub const PATH: &str = "/basic";
+
+        async fn page_handler() -> actix_web::HttpResponse {
+            let page = maud::html! {
+                @let my_action::Form { action, a_name, .. } = my_action::FORM;
+                form action=(action) {
+                    label { "A" input name=(a_name); }
+                }
+            };
+
+            actix_web::HttpResponse::Ok()
+                .content_type("text/html; charset=utf-8")
+                .body(page.into_string())
+        }
+
+        #[action]
+        async fn my_action(#[form] a: i32, state: web::Data<AppState>) -> String {
+            std::format!("a = {}", a + state.offset)
+        }
+
+        #[action]
+        async fn path_action(#[form] a: i32, request: actix_web::HttpRequest) -> String {
+            std::format!("{} a = {a}", request.path())
+        }
+
+        pub fn configure(config: &mut web::ServiceConfig) {
+            config.app_data(web::Data::new(AppState { offset: 10 }));
+            config.route(PATH, web::get().to(page_handler));
+            config.route(PATH, web::post().to(actions_handler));
+        }
+    }
+
+    assert_eq!(
+        post(page::configure, "/basic?/my_action", "a=1").await,
+        (StatusCode::OK, "a = 11".into())
+    );
+    assert_eq!(
+        post(page::configure, "/basic?/path_action", "a=1").await,
+        (StatusCode::OK, "/basic a = 1".into())
+    );
+    assert_eq!(
+        post(page::configure, "/basic?/my_action", "a=one").await,
+        (StatusCode::BAD_REQUEST, "Bad Form".into())
+    );
+    assert_eq!(
+        post(page::configure, "/basic?/missing", "a=1").await,
+        (StatusCode::NOT_FOUND, "Action Not Found".into())
+    );
+}
+
+#[actix_web::test]
+async fn named_handler() {
+    #[actions(actix_web(handler = named_actions_handler))]
+    mod page {
+        use actix_web::web;
+
+        use crate as html_form_actions;
+
+        #[action]
+        async fn my_action(#[form] a: i32) -> String {
+            std::format!("a = {a}")
+        }
+
+        pub fn configure(config: &mut web::ServiceConfig) {
+            config.route("/basic", web::post().to(named_actions_handler));
+        }
+    }
+
+    assert_eq!(
+        post(page::configure, "/basic?/my_action", "a=1").await,
+        (StatusCode::OK, "a = 1".into())
+    );
+}
+
+#[actix_web::test]
+async fn fail() {
+    #[actions(actix_web)]
+    mod page {
+        use actix_web::{HttpResponse, web};
+
+        use crate as html_form_actions;
+
+        use html_form_actions::{ActionResult, FieldError};
+
+        #[action(fail = my_action_failed)]
+        async fn my_action(#[form] a: i32) -> ActionResult<String, my_action::Errors> {
+            if a < 0 {
+                return Err(my_action::Errors {
+                    a: Some(FieldError::Message("A must not be negative")),
+                });
+            }
+
+            Ok(std::format!("a = {a}"))
+        }
+
+        async fn my_action_failed(failure: my_action::Failure<'_>) -> HttpResponse {
+            HttpResponse::UnprocessableEntity().body(std::format!(
+                "{}: {}",
+                failure.values.a.unwrap_or_default(),
+                failure.errors.a.unwrap()
+            ))
+        }
+
+        pub fn configure(config: &mut web::ServiceConfig) {
+            config.route("/basic", web::post().to(actions_handler));
+        }
+    }
+
+    assert_eq!(
+        post(page::configure, "/basic?/my_action", "a=1").await,
+        (StatusCode::OK, "a = 1".into())
+    );
+    assert_eq!(
+        post(page::configure, "/basic?/my_action", "a=-1").await,
+        (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "-1: A must not be negative".into()
+        )
+    );
+    assert_eq!(
+        post(page::configure, "/basic?/my_action", "a=x").await,
+        (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "x: This value is invalid".into()
+        )
+    );
+}
+
+#[actix_web::test]
+async fn reject() {
+    #[actions(actix_web, reject = rejected)]
+    mod page {
+        use actix_web::{HttpResponse, web};
+
+        use crate as html_form_actions;
+
+        use html_form_actions::Rejection;
+
+        #[action]
+        async fn my_action(#[form] a: i32, #[form] b: u8) -> String {
+            std::format!("a = {a}, b = {b}")
+        }
+
+        async fn rejected(rejection: Rejection<'_>) -> HttpResponse {
+            HttpResponse::BadRequest().body(rejection.to_string())
+        }
+
+        pub fn configure(config: &mut web::ServiceConfig) {
+            config.route("/basic", web::post().to(actions_handler));
+        }
+    }
+
+    assert_eq!(
+        post(page::configure, "/basic?/my_action", "a=1&b=300").await,
+        (StatusCode::BAD_REQUEST, "b: This value is invalid".into())
+    );
+}
@@ -1,5 +1,35 @@
+use axum::http::{StatusCode, header::CONTENT_TYPE};
+use tower::ServiceExt;
+
 use crate::{BuildExt, actions};
 
+/// Post the urlencoded `body` to `target`, such as `/basic?/my_action`, of the router built by `route`,
+/// returning the status and body of the response.
+async fn post(
+    route: fn(axum::Router) -> axum::Router,
+    target: &str,
+    body: &str,
+) -> (StatusCode, String) {
+    let request = axum::extract::Request::post(target)
+        .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+        .body(axum::body::Body::from(body.to_owned()))
+        .unwrap();
+
+    let response = axum::Router::new()
+        .with(route)
+        .oneshot(request)
+        .await
+        .unwrap();
+
+    let status = response.status();
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+
+    (status, String::from_utf8(body.into()).unwrap())
+}
+
 #[test]
 fn basic() {
     #[actions(axum)]
@@ -12,7 +42,7 @@ fn basic() {
 
         async fn page_handler() -> maud::PreEscaped<String> {
             maud::html! {
-                @let my_action::Form { action, a_name } = my_action::FORM;
+                @let my_action::Form { action, a_name, .. } = my_action::FORM;
                 form action=(action) {
                     label { "A" input name=(a_name); }
                 }
@@ -49,7 +79,7 @@ fn state() {
 
         async fn page_handler() -> maud::PreEscaped<String> {
             maud::html! {
-                @let my_action::Form { action, a_name } = my_action::FORM;
+                @let my_action::Form { action, a_name, .. } = my_action::FORM;
                 form action=(action) {
                     label { "A" input name=(a_name); }
                 }
@@ -84,7 +114,7 @@ fn named_handler() {
 
         async fn page_handler() -> maud::PreEscaped<String> {
             maud::html! {
-                @let my_action::Form { action, a_name } = my_action::FORM;
+                @let my_action::Form { action, a_name, .. } = my_action::FORM;
                 form action=(action) {
                     label { "A" input name=(a_name); }
                 }
@@ -103,3 +133,1133 @@ fn named_handler() {
 
     axum::Router::new().with(page::route).into_make_service();
 }
+
+#[tokio::test]
+async fn fail() {
+    #[actions(axum)]
+    mod page {
+        use axum::{http::StatusCode, routing::get};
+
+        use crate as html_form_actions;
+
+        use html_form_actions::{ActionResult, FieldError};
+
+        const PATH: &str = "/basic";
+
+        fn render(failure: Option<&my_action::Failure>) -> maud::Markup {
+            let values = failure.map(|failure| failure.values).unwrap_or_default();
+            let errors = failure.map(|failure| failure.errors).unwrap_or_default();
+
+            maud::html! {
+                @let my_action::Form { action, a_name, .. } = my_action::FORM;
+                form action=(action) {
+                    label { "A" input name=(a_name) value=[values.a]; }
+                    @if let Some(error) = errors.a {
+                        p { (error) }
+                    }
+                }
+            }
+        }
+
+        async fn page_handler() -> maud::Markup {
+            render(None)
+        }
+
+        #[action(fail = my_action_failed)]
+        async fn my_action(#[form] a: i32) -> ActionResult<String, my_action::Errors> {
+            if a < 0 {
+                return Err(my_action::Errors {
+                    a: Some(FieldError::Message("A must not be negative")),
+                });
+            }
+
+            Ok(std::format!("a = {a}"))
+        }
+
+        async fn my_action_failed(failure: my_action::Failure<'_>) -> (StatusCode, maud::Markup) {
+            (StatusCode::UNPROCESSABLE_ENTITY, render(Some(&failure)))
+        }
+
+        pub fn route(router: axum::Router) -> axum::Router {
+            router.route(PATH, get(page_handler).post(actions_handler))
+        }
+    }
+
+    assert_eq!(
+        post(page::route, "/basic?/my_action", "a=1").await,
+        (axum::http::StatusCode::OK, "a = 1".into())
+    );
+
+    assert_eq!(
+        post(page::route, "/basic?/my_action", "a=one").await,
+        (
+            axum::http::StatusCode::UNPROCESSABLE_ENTITY,
+            r#"<form action="?/my_action"><label>A<input name="a" value="one"></label><p>This value is invalid</p></form>"#.into()
+        )
+    );
+
+    assert_eq!(
+        post(page::route, "/basic?/my_action", "a=-1").await,
+        (
+            axum::http::StatusCode::UNPROCESSABLE_ENTITY,
+            r#"<form action="?/my_action"><label>A<input name="a" value="-1"></label><p>A must not be negative</p></form>"#.into()
+        )
+    );
+
+    assert_eq!(
+        post(page::route, "/basic?/my_action", "").await,
+        (
+            axum::http::StatusCode::UNPROCESSABLE_ENTITY,
+            r#"<form action="?/my_action"><label>A<input name="a"></label><p>This field is required</p></form>"#.into()
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_extracts_once() {
+    #[actions(axum)]
+    mod page {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        use axum::{extract::FromRequestParts, http::StatusCode, routing::post};
+
+        use crate as html_form_actions;
+
+        use html_form_actions::ActionResult;
+
+        const PATH: &str = "/basic";
+
+        pub static EXTRACTIONS: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(Clone)]
+        pub struct Extraction(usize);
+
+        impl<S: Sync> FromRequestParts<S> for Extraction {
+            type Rejection = core::convert::Infallible;
+
+            async fn from_request_parts(
+                _parts: &mut axum::http::request::Parts,
+                _state: &S,
+            ) -> Result<Self, Self::Rejection> {
+                Ok(Extraction(EXTRACTIONS.fetch_add(1, Ordering::Relaxed)))
+            }
+        }
+
+        #[action(fail = my_action_failed)]
+        async fn my_action(
+            #[form] a: i32,
+            Extraction(extraction): Extraction,
+        ) -> ActionResult<String, my_action::Errors> {
+            if a < 0 {
+                return Err(my_action::Errors::default());
+            }
+
+            Ok(std::format!("a = {a}, extraction = {extraction}"))
+        }
+
+        async fn my_action_failed(
+            _failure: my_action::Failure<'_>,
+            Extraction(extraction): Extraction,
+        ) -> (StatusCode, String) {
+            (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                std::format!("extraction = {extraction}"),
+            )
+        }
+
+        pub fn route(router: axum::Router) -> axum::Router {
+            router.route(PATH, post(actions_handler))
+        }
+    }
+
+    assert_eq!(
+        post(page::route, "/basic?/my_action", "a=1").await,
+        (axum::http::StatusCode::OK, "a = 1, extraction = 0".into())
+    );
+
+    assert_eq!(
+        post(page::route, "/basic?/my_action", "a=-1").await,
+        (
+            axum::http::StatusCode::UNPROCESSABLE_ENTITY,
+            "extraction = 1".into()
+        )
+    );
+
+    assert_eq!(
+        page::EXTRACTIONS.load(core::sync::atomic::Ordering::Relaxed),
+        2
+    );
+}
+
+#[tokio::test]
+async fn reject() {
+    #[actions(axum, reject = rejected)]
+    mod page {
+        use axum::{http::StatusCode, routing::post};
+
+        use crate as html_form_actions;
+
+        use html_form_actions::{FieldError, Rejection};
+
+        const PATH: &str = "/basic";
+
+        #[action]
+        async fn strict(#[form] a: i32, #[form] b: u8) -> String {
+            std::format!("a = {a}, b = {b}")
+        }
+
+        #[action]
+        async fn lenient(#[form] a: Result<i32, FieldError>, #[form] b: u8) -> String {
+            match a {
+                Ok(a) => std::format!("a = {a}, b = {b}"),
+                Err(error) => std::format!("a: {error}, b = {b}"),
+            }
+        }
+
+        async fn rejected(rejection: Rejection<'_>) -> (StatusCode, String) {
+            (
+                StatusCode::BAD_REQUEST,
+                std::format!("{}\n{rejection}", rejection.action),
+            )
+        }
+
+        pub fn route(router: axum::Router) -> axum::Router {
+            router.route(PATH, post(actions_handler))
+        }
+    }
+
+    assert_eq!(
+        post(page::route, "/basic?/strict", "a=1&b=2").await,
+        (axum::http::StatusCode::OK, "a = 1, b = 2".into())
+    );
+
+    assert_eq!(
+        post(page::route, "/basic?/strict", "a=one&b=256").await,
+        (
+            axum::http::StatusCode::BAD_REQUEST,
+            "?/strict\na: This value is invalid\nb: This value is invalid".into()
+        )
+    );
+
+    assert_eq!(
+        post(page::route, "/basic?/strict", "b=2").await,
+        (
+            axum::http::StatusCode::BAD_REQUEST,
+            "?/strict\na: This field is required".into()
+        )
+    );
+
+    assert_eq!(
+        post(page::route, "/basic?/lenient", "a=one&b=2").await,
+        (
+            axum::http::StatusCode::OK,
+            "a: This value is invalid, b = 2".into()
+        )
+    );
+
+    assert_eq!(
+        post(page::route, "/basic?/lenient", "a=one").await,
+        (
+            axum::http::StatusCode::BAD_REQUEST,
+            "?/lenient\nb: This field is required".into()
+        )
+    );
+}
+
+#[tokio::test]
+async fn constraints() {
+    #[actions(axum, reject = rejected)]
+    mod page {
+        use axum::{http::StatusCode, routing::get};
+
+        use crate as html_form_actions;
+
+        use html_form_actions::Rejection;
+
+        const PATH: &str = "/basic";
+
+        async fn page_handler() -> maud::PreEscaped<String> {
+            maud::html! {
+                @let my_action::Form {
+                    action,
+                    a_name,
+                    a_input_type,
+                    a_min,
+                    a_max,
+                    a_step,
+                    name_name,
+                    name_input_type,
+                    name_step: _,
+                    name_required,
+                    name_min_length,
+                    name_max_length,
+                    ..
+                } = my_action::FORM;
+                form action=(action) {
+                    label { "A" input type=(a_input_type) name=(a_name) min=(a_min) max=(a_max) step=[a_step]; }
+                    label {
+                        "Name"
+                        input type=(name_input_type) name=(name_name) required[name_required] minlength=(name_min_length) maxlength=[name_max_length];
+                    }
+                }
+            }
+        }
+
+        #[action]
+        async fn my_action(
+            #[form(min = -4, max = 10, step = 2)] a: i32,
+            #[form(required, min_length = 2, max_length = 5)] name: String,
+        ) -> String {
+            std::format!("a = {a}, name = {name}")
+        }
+
+        async fn rejected(rejection: Rejection<'_>) -> (StatusCode, String) {
+            (StatusCode::BAD_REQUEST, rejection.to_string())
+        }
+
+        pub fn route(router: axum::Router) -> axum::Router {
+            router.route(PATH, get(page_handler).post(actions_handler))
+        }
+
+        pub async fn render() -> String {
+            page_handler().await.into_string()
+        }
+    }
+
+    assert_eq!(
+        page::render().await,
+        r#"<form action="?/my_action"><label>A<input type="number" name="a" min="-4" max="10" step="2"></label><label>Name<input type="text" name="name" required minlength="2" maxlength="5"></label></form>"#
+    );
+
+    assert_eq!(
+        post(page::route, "/basic?/my_action", "a=4&name=Sam").await,
+        (axum::http::StatusCode::OK, "a = 4, name = Sam".into())
+    );
+
+    assert_eq!(
+        post(page::route, "/basic?/my_action", "a=-6&name=").await,
+        (
+            axum::http::StatusCode::BAD_REQUEST,
+            "a: This value must be at least -4\nname: This field is required".into()
+        )
+    );
+
+    assert_eq!(
+        post(page::route, "/basic?/my_action", "a=3&name=Samuel").await,
+        (
+            axum::http::StatusCode::BAD_REQUEST,
+            "a: This value must be in steps of 2\nname: Use at most 5 characters".into()
+        )
+    );
+
+    assert_eq!(
+        post(page::route, "/basic?/my_action", "a=12&name=S").await,
+        (
+            axum::http::StatusCode::BAD_REQUEST,
+            "a: This value must be at most 10\nname: Use at least 2 characters".into()
+        )
+    );
+}
+
+#[test]
+fn input_types() {
+    #[actions(axum)]
+    mod page {
+        use axum::routing::get;
+
+        use crate as html_form_actions;
+
+        const PATH: &str = "/basic";
+
+        async fn page_handler() -> maud::PreEscaped<String> {
+            render()
+        }
+
+        pub fn render() -> maud::PreEscaped<String> {
+            maud::html! {
+                @let my_action::Form {
+                    action,
+                    count_name,
+                    count_input_type,
+                    count_step,
+                    ratio_name,
+                    ratio_input_type,
+                    ratio_step,
+                    note_name,
+                    note_input_type,
+                    note_step,
+                    id_name,
+                    id_input_type,
+                    id_step: _,
+                    even_name,
+                    even_input_type,
+                    even_step,
+                    ..
+                } = my_action::FORM;
+                form action=(action) {
+                    input type=(count_input_type) name=(count_name) step=[count_step];
+                    input type=(ratio_input_type) name=(ratio_name) step=[ratio_step];
+                    input type=(note_input_type) name=(note_name) step=[note_step];
+                    input type=(id_input_type) name=(id_name);
+                    input type=(even_input_type) name=(even_name) step=[even_step];
+                }
+            }
+        }
+
+        #[action]
+        async fn my_action(
+            #[form] count: u32,
+            #[form] ratio: f64,
+            #[form] note: String,
+            #[form(input_type = "hidden")] id: u32,
+            #[form(step = 2)] even: i32,
+        ) -> String {
+            std::format!("{count} {ratio} {note} {id} {even}")
+        }
+
+        pub fn route(router: axum::Router) -> axum::Router {
+            router.route(PATH, get(page_handler).post(actions_handler))
+        }
+    }
+
+    axum::Router::new().with(page::route).into_make_service();
+
+    assert_eq!(
+        page::render().into_string(),
+        r#"<form action="?/my_action"><input type="number" name="count" step="1"><input type="number" name="ratio" step="any"><input type="text" name="note"><input type="hidden" name="id"><input type="number" name="even" step="2"></form>"#
+    );
+}
+
+#[test]
+fn ids() {
+    #[actions(axum)]
+    mod page {
+        use axum::routing::get;
+
+        use crate as html_form_actions;
+
+        use html_form_actions::{HtmlForm, IdExt};
+
+        const PATH: &str = "/basic";
+
+        #[derive(HtmlForm)]
+        pub struct Address {
+            #[form]
+            pub street: String,
+        }
+
+        async fn page_handler() -> maud::PreEscaped<String> {
+            render(3)
+        }
+
+        pub fn render(index: usize) -> maud::PreEscaped<String> {
+            maud::html! {
+                @let my_action::Form { action, value_name, value_id, value_error_id, address, .. } = my_action::FORM;
+                form action=(action) {
+                    label for=(value_id.with_suffix(index)) { "Value" }
+                    input id=(value_id.with_suffix(index)) name=(value_name) aria-describedby=(value_error_id.with_suffix(index));
+                    p id=(value_error_id.with_suffix(index)) { "Required" }
+                    input id=(my_action::FORM.address().street_id()) name=(address.street_name);
+                }
+                form action=(other::FORM.action) {
+                    input id=(other::FORM.address().street_id()) name=(other::FORM.address().street_name());
+                    input id=(other::FORM.addresses().at(2).street_id()) name=(other::FORM.addresses().at(2).street_name());
+                }
+            }
+        }
+
+        #[action]
+        async fn my_action(#[form] value: i32, #[form(flatten)] address: Address) -> String {
+            std::format!("{value} {}", address.street)
+        }
+
+        #[action]
+        async fn other(
+            #[form(flatten)] address: Address,
+            #[form(nested)] addresses: Vec<Address>,
+        ) -> String {
+            std::format!("{} {}", address.street, addresses.len())
+        }
+
+        pub fn route(router: axum::Router) -> axum::Router {
+            router.route(PATH, get(page_handler).post(actions_handler))
+        }
+    }
+
+    axum::Router::new().with(page::route).into_make_service();
+
+    assert_eq!(
+        page::render(3).into_string(),
+        r#"<form action="?/my_action"><label for="page-my_action-value-3">Value</label><input id="page-my_action-value-3" name="value" aria-describedby="page-my_action-value-error-3"><p id="page-my_action-value-error-3">Required</p><input id="page-my_action-street" name="street"></form><form action="?/other"><input id="page-other-street" name="street"><input id="page-other-addresses-2-street" name="addresses[2].street"></form>"#
+    );
+}
+
+#[test]
+fn labels() {
+    #[actions(axum)]
+    mod page {
+        use axum::routing::get;
+
+        use crate as html_form_actions;
+
+        const PATH: &str = "/basic";
+
+        async fn page_handler() -> maud::PreEscaped<String> {
+            render()
+        }
+
+        pub fn render() -> maud::PreEscaped<String> {
+            maud::html! {
+                @let my_action::Form {
+                    action,
+                    quantity_name,
+                    quantity_label,
+                    quantity_placeholder,
+                    quantity_help,
+                    unit_price_name,
+                    unit_price_label,
+                    unit_price_placeholder,
+                    unit_price_help,
+                    ..
+                } = my_action::FORM;
+                form action=(action) {
+                    label { (quantity_label) input name=(quantity_name) placeholder=[quantity_placeholder]; }
+                    @if let Some(help) = quantity_help {
+                        p { (help) }
+                    }
+                    label { (unit_price_label) input name=(unit_price_name) placeholder=[unit_price_placeholder]; }
+                    @if let Some(help) = unit_price_help {
+                        p { (help) }
+                    }
+                }
+            }
+        }
+
+        #[action]
+        async fn my_action(
+            #[form(label = "Quantity", placeholder = "0", help = "The number of items")]
+            quantity: u32,
+            #[form] unit_price: f64,
+        ) -> String {
+            std::format!("{quantity} {unit_price}")
+        }
+
+        pub fn route(router: axum::Router) -> axum::Router {
+            router.route(PATH, get(page_handler).post(actions_handler))
+        }
+    }
+
+    axum::Router::new().with(page::route).into_make_service();
+
+    assert_eq!(
+        page::render().into_string(),
+        r#"<form action="?/my_action"><label>Quantity<input name="quantity" placeholder="0"></label><p>The number of items</p><label>Unit price<input name="unit_price"></label></form>"#
+    );
+}
+
+#[tokio::test]
+async fn checkbox_and_optional() {
+    #[actions(axum)]
+    mod page {
+        use axum::routing::get;
+
+        use crate as html_form_actions;
+
+        const PATH: &str = "/basic";
+
+        async fn page_handler() -> maud::Markup {
+            maud::html! {
+                @let my_action::Form { action, flag_name, flag_input_type, note_name, count_name, .. } = my_action::FORM;
+                form action=(action) {
+                    input type=(flag_input_type) name=(flag_name);
+                    input name=(note_name);
+                    input name=(count_name);
+                }
+            }
+        }
+
+        #[action]
+        async fn my_action(
+            #[form] flag: bool,
+            #[form] note: Option<String>,
+            #[form] count: Option<u32>,
+        ) -> String {
+            std::format!("{flag} {note:?} {count:?}")
+        }
+
+        pub fn route(router: axum::Router) -> axum::Router {
+            router.route(PATH, get(page_handler).post(actions_handler))
+        }
+    }
+
+    let ok = |body: &str| (axum::http::StatusCode::OK, body.into());
+
+    assert_eq!(
+        post(page::route, "/basic?/my_action", "").await,
+        ok("false None None")
+    );
+    assert_eq!(
+        post(page::route, "/basic?/my_action", "flag=on&note=&count=").await,
+        ok("true None None")
+    );
+    assert_eq!(
+        post(
+            page::route,
+            "/basic?/my_action",
+            "flag=true&note=hi&count=3"
+        )
+        .await,
+        ok("true Some(\"hi\") Some(3)")
+    );
+    assert_eq!(
+        post(page::route, "/basic?/my_action", "flag=false").await,
+        ok("false None None")
+    );
+    assert_eq!(
+        post(page::route, "/basic?/my_action", "flag=yes").await,
+        (axum::http::StatusCode::BAD_REQUEST, "Bad Form".into())
+    );
+    assert_eq!(
+        post(page::route, "/basic?/my_action", "count=x").await,
+        (axum::http::StatusCode::BAD_REQUEST, "Bad Form".into())
+    );
+}
+
+#[tokio::test]
+async fn multiple() {
+    #[actions(axum)]
+    mod page {
+        use axum::routing::get;
+
+        use crate as html_form_actions;
+
+        const PATH: &str = "/basic";
+
+        pub fn render() -> maud::Markup {
+            maud::html! {
+                @let my_action::Form { action, tags_name, tags_input_type, tags_multiple, counts_name, counts_multiple, .. } = my_action::FORM;
+                form action=(action) {
+                    @for tag in ["a", "b", "c"] {
+                        input type=(tags_input_type) name=(tags_name) value=(tag);
+                    }
+                    select name=(counts_name) multiple[counts_multiple] {
+                        option { "1" }
+                        option { "2" }
+                    }
+                    @if tags_multiple {
+                        "multiple"
+                    }
+                }
+            }
+        }
+
+        async fn page_handler() -> maud::Markup {
+            render()
+        }
+
+        #[action]
+        async fn my_action(
+            #[form(input_type = "checkbox")] tags: Vec<String>,
+            #[form(max = 2)] counts: Vec<u32>,
+        ) -> String {
+            std::format!("{tags:?} {counts:?}")
+        }
+
+        pub fn route(router: axum::Router) -> axum::Router {
+            router.route(PATH, get(page_handler).post(actions_handler))
+        }
+    }
+
+    assert_eq!(
+        page::render().into_string(),
+        r#"<form action="?/my_action"><input type="checkbox" name="tags" value="a"><input type="checkbox" name="tags" value="b"><input type="checkbox" name="tags" value="c"><select name="counts" multiple><option>1</option><option>2</option></select>multiple</form>"#
+    );
+
+    let ok = |body: &str| (axum::http::StatusCode::OK, body.into());
+
+    assert_eq!(
+        post(page::route, "/basic?/my_action", "").await,
+        ok("[] []")
+    );
+    assert_eq!(
+        post(
+            page::route,
+            "/basic?/my_action",
+            "tags=a&counts=2&tags=c&counts=1"
+        )
+        .await,
+        ok("[\"a\", \"c\"] [2, 1]")
+    );
+    assert_eq!(
+        post(page::route, "/basic?/my_action", "tags=a&counts=1&counts=3").await,
+        (axum::http::StatusCode::BAD_REQUEST, "Bad Form".into())
+    );
+    assert_eq!(
+        post(page::route, "/basic?/my_action", "counts=1&counts=x").await,
+        (axum::http::StatusCode::BAD_REQUEST, "Bad Form".into())
+    );
+}
+
+#[tokio::test]
+async fn flatten() {
+    #[actions(axum, reject = rejected)]
+    mod page {
+        use axum::{http::StatusCode, routing::get};
+
+        use crate as html_form_actions;
+
+        use html_form_actions::{HtmlForm, Rejection};
+
+        const PATH: &str = "/basic";
+
+        #[derive(HtmlForm)]
+        struct Address {
+            #[form(required)]
+            street: String,
+            #[form(rename = "zip")]
+            postcode: Option<String>,
+        }
+
+        #[derive(HtmlForm)]
+        pub struct ProfileForm {
+            #[form(min_length = 2)]
+            name: String,
+            #[form(flatten)]
+            address: Address,
+            subscribe: bool,
+        }
+
+        pub fn render() -> maud::Markup {
+            maud::html! {
+                @let create::Form { action, profile, .. } = create::FORM;
+                @let profile_form::Form { name_name, name_min_length, address, subscribe_name, subscribe_input_type, .. } = profile;
+                @let address::Form { street_name, postcode_name, .. } = address;
+                form action=(action) {
+                    input name=(name_name) minlength=(name_min_length);
+                    input name=(street_name) required;
+                    input name=(postcode_name);
+                    input type=(subscribe_input_type) name=(subscribe_name);
+                }
+            }
+        }
+
+        async fn page_handler() -> maud::Markup {
+            render()
+        }
+
+        impl core::fmt::Display for ProfileForm {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                let Self {
+                    name,
+                    address: Address { street, postcode },
+                    subscribe,
+                } = self;
+
+                write!(f, "{name}, {street}, {postcode:?}, {subscribe}")
+            }
+        }
+
+        #[action]
+        async fn create(#[form(flatten)] profile: ProfileForm) -> String {
+            profile.to_string()
+        }
+
+        #[action]
+        async fn update(#[form] id: u32, #[form(flatten)] profile: ProfileForm) -> String {
+            std::format!("{id}: {profile}")
+        }
+
+        async fn rejected(rejection: Rejection<'_>) -> (StatusCode, String) {
+            (StatusCode::BAD_REQUEST, rejection.to_string())
+        }
+
+        pub fn route(router: axum::Router) -> axum::Router {
+            router.route(PATH, get(page_handler).post(actions_handler))
+        }
+    }
+
+    assert_eq!(
+        page::render().into_string(),
+        r#"<form action="?/create"><input name="name" minlength="2"><input name="street" required><input name="zip"><input type="checkbox" name="subscribe"></form>"#
+    );
+
+    assert_eq!(
+        post(
+            page::route,
+            "/basic?/create",
+            "name=Ann&street=High+St&zip=AB1&subscribe=on"
+        )
+        .await,
+        (
+            axum::http::StatusCode::OK,
+            r#"Ann, High St, Some("AB1"), true"#.into()
+        )
+    );
+
+    assert_eq!(
+        post(page::route, "/basic?/update", "id=3&name=Bob&street=Low+St").await,
+        (
+            axum::http::StatusCode::OK,
+            "3: Bob, Low St, None, false".into()
+        )
+    );
+
+    assert_eq!(
+        post(page::route, "/basic?/update", "name=B").await,
+        (
+            axum::http::StatusCode::BAD_REQUEST,
+            "id: This field is required\nname: Use at least 2 characters\nstreet: This field is required".into()
+        )
+    );
+}
+
+#[tokio::test]
+async fn nested() {
+    #[actions(axum, reject = rejected)]
+    mod page {
+        use axum::{http::StatusCode, routing::get};
+
+        use crate as html_form_actions;
+
+        use html_form_actions::{HtmlForm, Rejection};
+
+        const PATH: &str = "/basic";
+
+        #[derive(HtmlForm)]
+        struct Address {
+            #[form(required)]
+            street: String,
+        }
+
+        #[derive(HtmlForm)]
+        pub struct Item {
+            #[form(rename = "qty", min = 1)]
+            quantity: u32,
+            #[form(nested)]
+            address: Address,
+        }
+
+        pub fn render(count: usize) -> maud::Markup {
+            maud::html! {
+                @let update::Form { action, .. } = update::FORM;
+                @let item::Form { quantity_input_type, quantity_min, .. } = item::FORM;
+                form action=(action) {
+                    input name=(update::FORM.address().street_name());
+                    @for index in 0..count {
+                        @let item = update::FORM.items().at(index);
+                        input type=(quantity_input_type) min=(quantity_min) name=(item.quantity_name());
+                        input name=(item.address().street_name());
+                    }
+                }
+            }
+        }
+
+        async fn page_handler() -> maud::Markup {
+            render(1)
+        }
+
+        #[action]
+        async fn update(
+            #[form(nested)] address: Address,
+            #[form(nested)] items: Vec<Item>,
+        ) -> String {
+            let items = items
+                .iter()
+                .map(|item| std::format!("{} to {}", item.quantity, item.address.street))
+                .collect::<std::vec::Vec<_>>();
+
+            std::format!("{}: {}", address.street, items.join(", "))
+        }
+
+        async fn rejected(rejection: Rejection<'_>) -> (StatusCode, String) {
+            (StatusCode::BAD_REQUEST, rejection.to_string())
+        }
+
+        pub fn route(router: axum::Router) -> axum::Router {
+            router.route(PATH, get(page_handler).post(actions_handler))
+        }
+    }
+
+    assert_eq!(
+        page::render(2).into_string(),
+        r#"<form action="?/update"><input name="address.street"><input type="number" min="1" name="items[0].qty"><input name="items[0].address.street"><input type="number" min="1" name="items[1].qty"><input name="items[1].address.street"></form>"#
+    );
+
+    assert_eq!(
+        post(page::route, "/basic?/update", 
+            "address.street=Home&items%5B0%5D.qty=2&items%5B0%5D.address.street=A&items%5B1%5D.address.street=B&items%5B1%5D.qty=3"
+        )
+        .await,
+        (axum::http::StatusCode::OK, "Home: 2 to A, 3 to B".into())
+    );
+
+    assert_eq!(
+        post(page::route, "/basic?/update", "address.street=Home").await,
+        (axum::http::StatusCode::OK, "Home: ".into())
+    );
+
+    // Elements need not start from zero, or be contiguous, such as after a row is deleted
+    assert_eq!(
+        post(page::route, "/basic?/update", 
+            "address.street=Home&items%5B1%5D.qty=3&items%5B1%5D.address.street=B&items%5B4%5D.qty=5&items%5B4%5D.address.street=C"
+        )
+        .await,
+        (axum::http::StatusCode::OK, "Home: 3 to B, 5 to C".into())
+    );
+
+    // Elements are ordered by index, whatever the order of their fields
+    assert_eq!(
+        post(page::route, "/basic?/update", 
+            "address.street=Home&items%5B2%5D.qty=3&items%5B0%5D.qty=2&items%5B2%5D.address.street=B&items%5B0%5D.address.street=A"
+        )
+        .await,
+        (axum::http::StatusCode::OK, "Home: 2 to A, 3 to B".into())
+    );
+
+    assert_eq!(
+        post(
+            page::route,
+            "/basic?/update",
+            "items%5B0%5D.qty=0&items%5B0%5D.address.street=A"
+        )
+        .await,
+        (
+            axum::http::StatusCode::BAD_REQUEST,
+            "address: This field is required\nitems: This value must be at least 1".into()
+        )
+    );
+}
+
+#[tokio::test]
+async fn options() {
+    #[actions(axum)]
+    mod page {
+        use axum::routing::get;
+
+        use crate as html_form_actions;
+
+        use html_form_actions::FormOptions;
+
+        const PATH: &str = "/basic";
+
+        #[derive(Debug, FormOptions)]
+        pub enum Status {
+            Open,
+            InProgress,
+            #[form(value = "done", label = "Finished")]
+            Closed,
+        }
+
+        pub fn render() -> maud::Markup {
+            maud::html! {
+                @let my_action::Form { action, status_name, status_options, priority_name, priority_input_type, priority_options, .. } = my_action::FORM;
+                form action=(action) {
+                    select name=(status_name) {
+                        @for (value, label) in status_options {
+                            option value=(value) { (label) }
+                        }
+                    }
+                    @for (value, label) in priority_options {
+                        label { input type=(priority_input_type) name=(priority_name) value=(value); (label) }
+                    }
+                }
+            }
+        }
+
+        async fn page_handler() -> maud::Markup {
+            render()
+        }
+
+        #[action]
+        async fn my_action(#[form] status: Status, #[form] priority: Option<Status>) -> String {
+            std::format!("{status:?} {priority:?}")
+        }
+
+        pub fn route(router: axum::Router) -> axum::Router {
+            router.route(PATH, get(page_handler).post(actions_handler))
+        }
+    }
+
+    assert_eq!(
+        page::render().into_string(),
+        r#"<form action="?/my_action"><select name="status"><option value="open">Open</option><option value="in_progress">In progress</option><option value="done">Finished</option></select><label><input type="radio" name="priority" value="open">Open</label><label><input type="radio" name="priority" value="in_progress">In progress</label><label><input type="radio" name="priority" value="done">Finished</label></form>"#
+    );
+
+    let ok = |body: &str| (axum::http::StatusCode::OK, body.into());
+
+    assert_eq!(
+        post(page::route, "/basic?/my_action", "status=open").await,
+        ok("Open None")
+    );
+    assert_eq!(
+        post(
+            page::route,
+            "/basic?/my_action",
+            "status=in_progress&priority=done"
+        )
+        .await,
+        ok("InProgress Some(Closed)")
+    );
+    assert_eq!(
+        post(page::route, "/basic?/my_action", "status=Closed").await,
+        (axum::http::StatusCode::BAD_REQUEST, "Bad Form".into())
+    );
+}
+
+#[tokio::test]
+async fn submission() {
+    #[actions(axum)]
+    mod page {
+        use axum::routing::post;
+
+        use crate as html_form_actions;
+
+        use html_form_actions::HtmlForm;
+
+        const PATH: &str = "/basic";
+
+        #[derive(HtmlForm)]
+        pub struct Row {
+            #[form]
+            pub value: i32,
+        }
+
+        #[action]
+        async fn my_action(
+            #[form] name: String,
+            #[form] tags: Vec<String>,
+            #[form(nested)] rows: Vec<Row>,
+        ) -> String {
+            let rows = rows.iter().map(|row| row.value).sum::<i32>();
+
+            std::format!("{name} {tags:?} {rows}")
+        }
+
+        pub fn route(router: axum::Router) -> axum::Router {
+            router.route(PATH, post(actions_handler))
+        }
+
+        fn values() -> my_action::Values<'static> {
+            my_action::Values {
+                name: Some("A & B"),
+                tags: vec!["x", "y z"],
+                rows: vec![
+                    (0, row::Values { value: Some("1") }),
+                    (1, row::Values { value: Some("2") }),
+                ],
+            }
+        }
+
+        /// The target and body of the submission of `values`.
+        pub fn submission() -> (String, String) {
+            let values = values();
+            let submission = my_action::submission(&values);
+
+            (
+                std::format!("{PATH}{}", submission.target()),
+                submission.body().to_string(),
+            )
+        }
+
+        pub fn body() -> String {
+            values().to_urlencoded().to_string()
+        }
+
+        /// The body of the submission of the same values as `values`, from typed values.
+        pub fn typed_body() -> String {
+            let values = my_action::Typed {
+                name: "A & B",
+                tags: vec!["x", "y z"],
+                rows: vec![row::Typed { value: 1 }, row::Typed { value: 2 }],
+            };
+
+            my_action::typed_submission(&values).body().to_string()
+        }
+
+        #[cfg(feature = "serde")]
+        pub fn serialized() -> String {
+            serde_urlencoded::to_string(my_action::submission(&values())).unwrap()
+        }
+    }
+
+    assert_eq!(
+        page::body(),
+        "name=A+%26+B&tags=x&tags=y+z&rows%5B0%5D.value=1&rows%5B1%5D.value=2"
+    );
+
+    assert_eq!(page::typed_body(), page::body());
+
+    #[cfg(feature = "serde")]
+    assert_eq!(page::serialized(), page::body());
+
+    let (target, body) = page::submission();
+
+    assert_eq!(
+        post(page::route, &target, &body).await,
+        (axum::http::StatusCode::OK, r#"A & B ["x", "y z"] 3"#.into())
+    );
+}
+
+#[cfg(feature = "axum")]
+#[tokio::test]
+async fn client() {
+    use crate::testing::axum::{Client, TestResponse};
+
+    #[actions(axum)]
+    mod page {
+        use axum::routing::post;
+
+        use crate as html_form_actions;
+
+        use html_form_actions::testing::axum::{Client, TestResponse};
+
+        pub const PATH: &str = "/basic";
+
+        #[action]
+        async fn my_action(#[form] a: i32, #[form] b: Option<String>) -> String {
+            std::format!("a = {a}, b = {b:?}")
+        }
+
+        pub fn route(router: axum::Router) -> axum::Router {
+            router.route(PATH, post(actions_handler))
+        }
+
+        pub async fn submit(client: &Client, a: i32) -> TestResponse {
+            client
+                .submit(my_action::typed_submission(&my_action::Typed {
+                    a,
+                    b: Some("x & y"),
+                }))
+                .await
+        }
+
+        pub async fn submit_raw(client: &Client, a: &'static str) -> TestResponse {
+            client
+                .submit_raw::<my_action::Action>(my_action::Values {
+                    a: Some(a),
+                    b: None,
+                })
+                .await
+        }
+    }
+
+    let client = Client::new(axum::Router::new().with(page::route), page::PATH);
+
+    let TestResponse {
+        status,
+        headers,
+        body,
+    } = page::submit(&client, 1).await;
+
+    assert_eq!(status, axum::http::StatusCode::OK);
+    assert_eq!(
+        headers[axum::http::header::CONTENT_TYPE],
+        "text/plain; charset=utf-8"
+    );
+    assert_eq!(body, r#"a = 1, b = Some("x & y")"#);
+
+    assert_eq!(
+        page::submit_raw(&client, "one").await.status,
+        axum::http::StatusCode::BAD_REQUEST
+    );
+}
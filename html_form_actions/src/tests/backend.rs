@@ -0,0 +1,151 @@
+use crate::{ActionBackend, FromActionRequest, IntoActionResponse, actions};
+
+struct TestRequest {
+    query: Option<&'static str>,
+    body: Vec<u8>,
+    user: Option<&'static str>,
+}
+
+impl TestRequest {
+    fn new(query: &'static str, body: &str) -> Self {
+        Self {
+            query: Some(query),
+            body: body.into(),
+            user: Some("alice"),
+        }
+    }
+}
+
+struct TestBackend;
+
+impl ActionBackend for TestBackend {
+    type Request = TestRequest;
+    type Response = (u16, String);
+    type Body<'r> = Vec<u8>;
+
+    fn query(request: &Self::Request) -> Option<&str> {
+        request.query
+    }
+
+    async fn body(request: &mut Self::Request) -> Result<Self::Body<'_>, Self::Response> {
+        Ok(std::mem::take(&mut request.body))
+    }
+
+    fn not_found() -> Self::Response {
+        (404, "Action Not Found".into())
+    }
+
+    fn bad_request(reason: &'static str) -> Self::Response {
+        (400, reason.into())
+    }
+}
+
+struct User(&'static str);
+
+impl FromActionRequest<TestBackend> for User {
+    async fn from_action_request(request: &mut TestRequest) -> Result<Self, (u16, String)> {
+        request.user.map(User).ok_or((401, "Unauthorized".into()))
+    }
+}
+
+impl IntoActionResponse<TestBackend> for String {
+    fn into_action_response(self) -> (u16, String) {
+        (200, self)
+    }
+}
+
+impl IntoActionResponse<TestBackend> for (u16, String) {
+    fn into_action_response(self) -> (u16, String) {
+        self
+    }
+}
+
+#[actions(backend(with = super::TestBackend), reject = rejected)]
+mod page {
+    use crate as html_form_actions;
+
+    use html_form_actions::{ActionResult, FieldError, Rejection};
+
+    use super::{TestRequest, User};
+
+    #[action]
+    async fn greet(#[form] name: String, user: User) -> String {
+        std::format!("{}: hello {name}", user.0)
+    }
+
+    #[action(fail = set_failed)]
+    async fn set(#[form] value: i32) -> ActionResult<String, set::Errors> {
+        if value < 0 {
+            return Err(set::Errors {
+                value: Some(FieldError::Message("Value must not be negative")),
+            });
+        }
+
+        Ok(std::format!("value = {value}"))
+    }
+
+    async fn set_failed(failure: set::Failure<'_>) -> (u16, String) {
+        (
+            422,
+            std::format!(
+                "{:?}: {}",
+                failure.values.value,
+                failure.errors.value.unwrap()
+            ),
+        )
+    }
+
+    async fn rejected(rejection: Rejection<'_>) -> (u16, String) {
+        (400, rejection.to_string())
+    }
+
+    pub async fn dispatch(request: TestRequest) -> (u16, String) {
+        dispatch_action(request).await
+    }
+}
+
+#[tokio::test]
+async fn dispatch() {
+    assert_eq!(
+        page::dispatch(TestRequest::new("/greet", "name=Bob")).await,
+        (200, "alice: hello Bob".into())
+    );
+    assert_eq!(
+        page::dispatch(TestRequest::new("/set", "value=3")).await,
+        (200, "value = 3".into())
+    );
+}
+
+#[tokio::test]
+async fn extractor_rejection() {
+    let request = TestRequest {
+        user: None,
+        ..TestRequest::new("/greet", "name=Bob")
+    };
+
+    assert_eq!(page::dispatch(request).await, (401, "Unauthorized".into()));
+}
+
+#[tokio::test]
+async fn failures() {
+    assert_eq!(
+        page::dispatch(TestRequest::new("/set", "value=-1")).await,
+        (422, r#"Some("-1"): Value must not be negative"#.into())
+    );
+    assert_eq!(
+        page::dispatch(TestRequest::new("/set", "value=x")).await,
+        (422, r#"Some("x"): This value is invalid"#.into())
+    );
+    assert_eq!(
+        page::dispatch(TestRequest::new("/greet", "")).await,
+        (400, "name: This field is required".into())
+    );
+    assert_eq!(
+        page::dispatch(TestRequest::new("/greet", "name=%ff")).await,
+        (400, "Form is not valid UTF-8".into())
+    );
+    assert_eq!(
+        page::dispatch(TestRequest::new("/remove", "")).await,
+        (404, "Action Not Found".into())
+    );
+}
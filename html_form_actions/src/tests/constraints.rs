@@ -0,0 +1,158 @@
+use crate::{Constraints, FieldError};
+
+#[test]
+fn required() {
+    let constraints = Constraints {
+        required: true,
+        ..Constraints::NONE
+    };
+
+    assert_eq!(constraints.check(None), Err(FieldError::Missing));
+    assert_eq!(constraints.check(Some("")), Err(FieldError::Missing));
+    assert_eq!(constraints.check(Some("a")), Ok(()));
+}
+
+#[test]
+fn empty_values_are_only_checked_if_required() {
+    let constraints = Constraints {
+        min_length: Some(2),
+        min: Some("5"),
+        ..Constraints::NONE
+    };
+
+    assert_eq!(constraints.check(None), Ok(()));
+    assert_eq!(constraints.check(Some("")), Ok(()));
+}
+
+#[test]
+fn length() {
+    let constraints = Constraints {
+        min_length: Some(2),
+        max_length: Some(3),
+        ..Constraints::NONE
+    };
+
+    assert_eq!(
+        constraints.check(Some("a")),
+        Err(FieldError::TooShort { min_length: 2 })
+    );
+    assert_eq!(constraints.check(Some("ab")), Ok(()));
+    assert_eq!(constraints.check(Some("äöü")), Ok(()));
+    assert_eq!(
+        constraints.check(Some("abcd")),
+        Err(FieldError::TooLong { max_length: 3 })
+    );
+}
+
+#[test]
+fn numeric_range_and_step() {
+    let constraints = Constraints {
+        min: Some("-1"),
+        max: Some("10"),
+        step: Some("0.5"),
+        ..Constraints::NONE
+    };
+
+    assert_eq!(
+        constraints.check(Some("-2")),
+        Err(FieldError::RangeUnderflow { min: "-1" })
+    );
+    assert_eq!(constraints.check(Some("-1")), Ok(()));
+    assert_eq!(constraints.check(Some("2.5")), Ok(()));
+    assert_eq!(
+        constraints.check(Some("2.7")),
+        Err(FieldError::StepMismatch { step: "0.5" })
+    );
+    assert_eq!(constraints.check(Some("10")), Ok(()));
+    assert_eq!(
+        constraints.check(Some("11")),
+        Err(FieldError::RangeOverflow { max: "10" })
+    );
+}
+
+#[test]
+fn date_range() {
+    let constraints = Constraints {
+        min: Some("2024-01-01"),
+        max: Some("2024-12-31"),
+        ..Constraints::NONE
+    };
+
+    assert_eq!(
+        constraints.check(Some("2023-12-31")),
+        Err(FieldError::RangeUnderflow { min: "2024-01-01" })
+    );
+    assert_eq!(constraints.check(Some("2024-06-15")), Ok(()));
+    assert_eq!(
+        constraints.check(Some("2025-01-01")),
+        Err(FieldError::RangeOverflow { max: "2024-12-31" })
+    );
+}
+
+#[test]
+fn multiple_values() {
+    let constraints = Constraints {
+        required: true,
+        max: Some("10"),
+        ..Constraints::NONE
+    };
+
+    assert_eq!(constraints.check_all(&[]), Err(FieldError::Missing));
+    assert_eq!(constraints.check_all(&["1", "10"]), Ok(()));
+    assert_eq!(
+        constraints.check_all(&["1", "11"]),
+        Err(FieldError::RangeOverflow { max: "10" })
+    );
+    assert_eq!(Constraints::NONE.check_all(&[]), Ok(()));
+}
+
+#[cfg(feature = "pattern")]
+#[test]
+fn pattern() {
+    static PATTERN: crate::Pattern = crate::Pattern::new("[a-z]+|[0-9]+");
+
+    let constraints = Constraints {
+        pattern: Some(&PATTERN),
+        ..Constraints::NONE
+    };
+
+    assert_eq!(constraints.check(Some("abc")), Ok(()));
+    assert_eq!(constraints.check(Some("123")), Ok(()));
+    assert_eq!(
+        constraints.check(Some("abc123")),
+        Err(FieldError::PatternMismatch)
+    );
+}
+
+#[cfg(feature = "pattern")]
+#[test]
+fn action_pattern() {
+    #[crate::actions(parse)]
+    mod page {
+        use crate as html_form_actions;
+
+        #[action]
+        fn rename(#[form(pattern = "[a-z]+")] name: String) -> std::string::String {
+            name
+        }
+
+        pub fn parse(body: &str) -> Result<std::string::String, std::string::String> {
+            let mut body = body.as_bytes().to_vec();
+
+            match ParsedAction::parse("rename", &mut body) {
+                Ok(ParsedAction::Rename { name }) => Ok(rename(name)),
+                Err(error) => Err(std::format!("{error:?}")),
+            }
+        }
+    }
+
+    // The pattern is compiled by the first submission, and reused by the second
+    for _ in 0..2 {
+        assert_eq!(page::parse("name=abc"), Ok("abc".into()));
+        assert!(
+            page::parse("name=ABC")
+                .unwrap_err()
+                .contains("PatternMismatch")
+        );
+    }
+}
@@ -0,0 +1,287 @@
+#![cfg(feature = "embedded_io")]
+
+use core::convert::Infallible;
+
+use embedded_io_async::{ErrorType, Read};
+
+use crate::{
+    actions,
+    embedded_io::{ReadError, Request, Response, TooManyHeaders},
+};
+
+/// A connection which returns a single byte from each read, as a slow network might.
+struct Trickle<'a>(&'a [u8]);
+
+impl ErrorType for Trickle<'_> {
+    type Error = Infallible;
+}
+
+impl Read for Trickle<'_> {
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Infallible> {
+        let Some((&byte, rest)) = self.0.split_first() else {
+            return Ok(0);
+        };
+
+        buf[0] = byte;
+        self.0 = rest;
+
+        Ok(1)
+    }
+}
+
+fn post(target: &str, body: &str) -> std::string::String {
+    std::format!(
+        "POST {target} HTTP/1.1\r\n\
+        Host: device.local\r\n\
+        Content-Type: application/x-www-form-urlencoded\r\n\
+        Content-Length: {}\r\n\
+        \r\n\
+        {body}",
+        body.len()
+    )
+}
+
+async fn written<const N: usize>(response: &Response<N>) -> std::string::String {
+    let mut buffer = [0; 512];
+    let mut connection = &mut buffer[..];
+
+    response.write(&mut connection).await.unwrap();
+
+    let length = 512 - connection.len();
+
+    std::str::from_utf8(&buffer[..length]).unwrap().into()
+}
+
+#[tokio::test]
+async fn basic() {
+    #[actions(embedded_io(capacity = 256))]
+    mod page {
+        use core::fmt::Write;
+
+        use crate as html_form_actions;
+
+        use html_form_actions::{
+            FromActionRequest,
+            embedded_io::{EmbeddedIo, Request, Response},
+        };
+
+        pub struct Host(heapless::String<32>);
+
+        impl<R, const N: usize> FromActionRequest<EmbeddedIo<R, N>> for Host {
+            async fn from_action_request(request: &mut Request<R, N>) -> Result<Self, Response<N>> {
+                request
+                    .header("Host")
+                    .and_then(|host| host.try_into().ok())
+                    .map(Self)
+                    .ok_or(Response::new(400, "Missing Host"))
+            }
+        }
+
+        #[action]
+        async fn my_action(#[form] a: i32, Host(host): Host) -> heapless::String<256> {
+            let mut response = heapless::String::new();
+
+            write!(response, "{host}: a = {a}").unwrap();
+
+            response
+        }
+
+        #[action]
+        async fn tags(#[form] tags: heapless::Vec<char, 2>) -> (u16, &'static str) {
+            (201, if tags.contains(&'x') { "x" } else { "no x" })
+        }
+
+        #[action]
+        async fn rename(#[form] name: heapless::String<16>) -> heapless::String<256> {
+            let mut response = heapless::String::new();
+
+            write!(response, "name = {name}").unwrap();
+
+            response
+        }
+
+        #[action]
+        async fn redirect(#[form] _confirm: bool) -> Response<256> {
+            Response::see_other("/page")
+        }
+
+        /// The "maxlength" of the name, which is a quarter of the capacity of its `heapless::String`.
+        pub const NAME_MAX_LENGTH: Option<usize> = rename::FORM.name_max_length;
+
+        pub async fn handle<R>(request: Request<R, 256>) -> Response<256> {
+            actions_handler(request).await
+        }
+    }
+
+    let request = post("/page?/my_action", "a=1");
+    let request = Request::<_, 256>::read(Trickle(request.as_bytes()))
+        .await
+        .unwrap();
+
+    assert_eq!(request.method(), "POST");
+    assert_eq!(request.path(), "/page");
+    assert_eq!(request.query(), Some("/my_action"));
+    assert_eq!(request.header("content-length"), Some("3"));
+
+    let response = page::handle(request).await;
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.body(), "device.local: a = 1");
+
+    let request = post("/page?/tags", "tags=x&tags=y");
+    let response = page::handle(Request::read(request.as_bytes()).await.unwrap()).await;
+
+    assert_eq!((response.status(), response.body()), (201, "x"));
+
+    let request = post("/page?/tags", "tags=x&tags=y&tags=z");
+    let response = page::handle(Request::read(request.as_bytes()).await.unwrap()).await;
+
+    assert_eq!((response.status(), response.body()), (400, "Bad Form"));
+
+    assert_eq!(page::NAME_MAX_LENGTH, Some(4));
+
+    let request = post("/page?/rename", "name=four");
+    let response = page::handle(Request::read(request.as_bytes()).await.unwrap()).await;
+
+    assert_eq!((response.status(), response.body()), (200, "name = four"));
+
+    let request = post("/page?/rename", "name=five%21");
+    let response = page::handle(Request::read(request.as_bytes()).await.unwrap()).await;
+
+    assert_eq!(response.status(), 400);
+
+    let request = post("/page?/redirect", "_confirm=on");
+    let response = page::handle(Request::read(request.as_bytes()).await.unwrap()).await;
+
+    assert_eq!(
+        written(&response).await,
+        "HTTP/1.1 303 See Other\r\n\
+        Content-Type: text/plain; charset=utf-8\r\n\
+        Content-Length: 0\r\n\
+        Connection: close\r\n\
+        Location: /page\r\n\
+        \r\n"
+    );
+
+    let request = post("/page?/missing", "a=1");
+    let response = page::handle(Request::read(request.as_bytes()).await.unwrap()).await;
+
+    assert_eq!(
+        written(&response).await,
+        "HTTP/1.1 404 Not Found\r\n\
+        Content-Type: text/plain; charset=utf-8\r\n\
+        Content-Length: 16\r\n\
+        Connection: close\r\n\
+        \r\n\
+        Action Not Found"
+    );
+}
+
+#[tokio::test]
+async fn read_errors() {
+    async fn read<const N: usize>(
+        request: &str,
+    ) -> Result<Request<&[u8], N>, ReadError<Infallible>> {
+        Request::read(request.as_bytes()).await
+    }
+
+    let request = post("/page?/my_action", "a=123456789");
+
+    // The head and the body share the capacity of the request
+    assert_eq!(
+        read::<160>(&request)
+            .await
+            .map(|request| request.body().len()),
+        Ok(11)
+    );
+    assert_eq!(
+        read::<96>(&request).await.err(),
+        Some(ReadError::HeadTooLarge)
+    );
+    assert_eq!(
+        read::<128>(&request).await.err(),
+        Some(ReadError::BodyTooLarge)
+    );
+    assert_eq!(
+        read::<160>(&request[..request.len() - 1]).await.err(),
+        Some(ReadError::UnexpectedEof)
+    );
+    assert_eq!(
+        read::<128>("POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n3\r\na=1\r\n0\r\n\r\n")
+            .await
+            .err(),
+        Some(ReadError::UnsupportedTransferEncoding)
+    );
+    assert_eq!(
+        read::<128>("POST\r\n\r\n").await.err(),
+        Some(ReadError::InvalidHead)
+    );
+    assert_eq!(
+        read::<128>("POST / HTTP/1.1\r\nContent-Length: many\r\n\r\n")
+            .await
+            .err(),
+        Some(ReadError::InvalidHead)
+    );
+
+    assert_eq!(
+        ReadError::<Infallible>::BodyTooLarge
+            .response::<16>()
+            .map(|response| response.status()),
+        Some(413)
+    );
+    assert_eq!(
+        ReadError::<Infallible>::UnsupportedTransferEncoding
+            .response::<16>()
+            .map(|response| response.status()),
+        Some(411)
+    );
+    assert!(
+        ReadError::<Infallible>::UnexpectedEof
+            .response::<16>()
+            .is_none()
+    );
+}
+
+#[test]
+fn headers() {
+    let response = ["A", "B", "C"]
+        .into_iter()
+        .try_fold(Response::<16>::see_other("/page"), |response, name| {
+            response.with_header(name, "value")
+        })
+        .unwrap();
+
+    assert_eq!(response.header("location"), Some("/page"));
+    assert_eq!(response.header("c"), Some("value"));
+    assert_eq!(
+        response.with_header("D", "value").err(),
+        Some(TooManyHeaders)
+    );
+}
+
+#[tokio::test]
+async fn generic_capacity() {
+    #[actions(embedded_io(handler = dispatch))]
+    mod page {
+        use crate as html_form_actions;
+
+        use html_form_actions::embedded_io::{Request, Response};
+
+        #[action]
+        async fn toggle(#[form] on: bool) -> &'static str {
+            if on { "on" } else { "off" }
+        }
+
+        pub async fn handle<R, const N: usize>(request: Request<R, N>) -> Response<N> {
+            dispatch(request).await
+        }
+    }
+
+    let request = post("/page?/toggle", "on=on");
+
+    let response = page::handle(Request::<_, 128>::read(request.as_bytes()).await.unwrap()).await;
+    assert_eq!(response.body(), "on");
+
+    let response = page::handle(Request::<_, 256>::read(request.as_bytes()).await.unwrap()).await;
+    assert_eq!(response.body(), "on");
+}
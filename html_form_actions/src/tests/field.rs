@@ -0,0 +1,188 @@
+use crate::{FieldError, FormField};
+
+#[test]
+fn input_types() {
+    assert_eq!(<i32 as FormField>::INPUT_TYPE, "number");
+    assert_eq!(<i32 as FormField>::STEP, Some("1"));
+    assert_eq!(<f32 as FormField>::STEP, Some("any"));
+    assert_eq!(<bool as FormField>::INPUT_TYPE, "checkbox");
+    assert_eq!(<String as FormField>::INPUT_TYPE, "text");
+    assert_eq!(<String as FormField>::STEP, None);
+    assert_eq!(<Result<u8, FieldError> as FormField>::INPUT_TYPE, "number");
+    assert_eq!(<Option<u8> as FormField>::INPUT_TYPE, "number");
+}
+
+#[test]
+fn checkbox() {
+    assert_eq!(bool::from_form_field(&None), Ok(false));
+    assert_eq!(bool::from_form_field(&Some("on")), Ok(true));
+    assert_eq!(bool::from_form_field(&Some("true")), Ok(true));
+    assert_eq!(bool::from_form_field(&Some("false")), Ok(false));
+    assert_eq!(bool::from_form_field(&Some("")), Err(FieldError::Invalid));
+    assert_eq!(
+        bool::from_form_field(&Some("yes")),
+        Err(FieldError::Invalid)
+    );
+    assert_eq!(
+        Result::<bool, FieldError>::from_form_field(&None),
+        Ok(Ok(false))
+    );
+}
+
+#[test]
+fn optional() {
+    assert_eq!(Option::<i32>::from_form_field(&None), Ok(None));
+    assert_eq!(Option::<i32>::from_form_field(&Some("")), Ok(None));
+    assert_eq!(Option::<i32>::from_form_field(&Some("12")), Ok(Some(12)));
+    assert_eq!(
+        Option::<i32>::from_form_field(&Some("x")),
+        Err(FieldError::Invalid)
+    );
+    assert_eq!(Option::<String>::from_form_field(&Some("")), Ok(None));
+    assert_eq!(i32::from_form_field(&None), Err(FieldError::Missing));
+    assert_eq!(String::from_form_field(&Some("")), Ok(String::new()));
+}
+
+#[test]
+fn multiple() {
+    use crate::FieldValues;
+
+    assert_eq!(<Vec<i32> as FormField>::INPUT_TYPE, "number");
+
+    let mut values = Vec::new();
+
+    assert_eq!(Vec::<i32>::from_form_field(&values), Ok(Vec::new()));
+
+    FieldValues::push(&mut values, "1").unwrap();
+    FieldValues::push(&mut values, "2").unwrap();
+
+    assert_eq!(values.as_slice(), ["1", "2"]);
+    assert_eq!(Vec::<i32>::from_form_field(&values), Ok(vec![1, 2]));
+
+    FieldValues::push(&mut values, "x").unwrap();
+
+    assert_eq!(
+        Vec::<i32>::from_form_field(&values),
+        Err(FieldError::Invalid)
+    );
+
+    let mut value = None;
+
+    FieldValues::push(&mut value, "1").unwrap();
+    FieldValues::push(&mut value, "2").unwrap();
+
+    assert_eq!(value, Some("2"));
+}
+
+#[test]
+fn options() {
+    use crate as html_form_actions;
+
+    use html_form_actions::{FormOptions, FromFormValue};
+
+    #[derive(Debug, PartialEq, FormOptions)]
+    enum Size {
+        Small,
+        #[form(label = "Medium size")]
+        Medium,
+        #[form(value = "xl")]
+        ExtraLarge,
+    }
+
+    assert_eq!(
+        <Size as FormOptions>::OPTIONS,
+        [
+            ("small", "Small"),
+            ("medium", "Medium size"),
+            ("xl", "Extra large")
+        ]
+    );
+    assert_eq!(<Size as FormField>::OPTIONS, <Size as FormOptions>::OPTIONS);
+    assert_eq!(
+        <Vec<Size> as FormField>::OPTIONS,
+        <Size as FormOptions>::OPTIONS
+    );
+    assert!(<i32 as FormField>::OPTIONS.is_empty());
+
+    for size in [Size::Small, Size::Medium, Size::ExtraLarge] {
+        assert_eq!(Size::from_form_value(size.value()), Ok(size));
+    }
+
+    assert_eq!(Size::ExtraLarge.label(), "Extra large");
+    assert_eq!(Size::from_form_value("Small"), Err(FieldError::Invalid));
+}
+
+#[cfg(feature = "heapless")]
+#[test]
+fn heapless() {
+    use crate::{FieldValues, FromFormValue};
+
+    let mut values = heapless::Vec::<&str, 2>::new();
+
+    FieldValues::push(&mut values, "1").unwrap();
+    FieldValues::push(&mut values, "2").unwrap();
+
+    assert_eq!(
+        FieldValues::push(&mut values, "3"),
+        Err(FieldError::TooManyValues { max_values: 2 })
+    );
+    assert_eq!(
+        heapless::Vec::<i32, 2>::from_form_field(&values).map(|values| values.into_array()),
+        Ok(Ok([1, 2]))
+    );
+
+    assert_eq!(<heapless::String<8> as FormField>::MAX_LENGTH, Some(2));
+    assert_eq!(
+        <Option<heapless::String<8>> as FormField>::MAX_LENGTH,
+        Some(2)
+    );
+    assert_eq!(<String as FormField>::MAX_LENGTH, None);
+    assert_eq!(
+        heapless::String::<8>::from_form_value("🦀🦀").as_deref(),
+        Ok("🦀🦀")
+    );
+    assert_eq!(
+        heapless::String::<8>::from_form_value("ab").as_deref(),
+        Ok("ab")
+    );
+    assert_eq!(
+        heapless::String::<8>::from_form_value("abc"),
+        Err(FieldError::TooLong { max_length: 2 })
+    );
+    assert_eq!(
+        heapless::String::<8>::from_form_value("🦀🦀🦀"),
+        Err(FieldError::TooLong { max_length: 2 })
+    );
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn chrono() {
+    use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+
+    use crate::FromFormValue;
+
+    assert_eq!(<NaiveDate as FormField>::INPUT_TYPE, "date");
+    assert_eq!(<NaiveTime as FormField>::INPUT_TYPE, "time");
+    assert_eq!(<NaiveDateTime as FormField>::INPUT_TYPE, "datetime-local");
+
+    let date = NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+
+    assert_eq!(NaiveDate::from_form_value("2024-02-29"), Ok(date));
+    assert_eq!(
+        NaiveDate::from_form_value("2023-02-29"),
+        Err(FieldError::Invalid)
+    );
+    assert_eq!(
+        NaiveTime::from_form_value("09:30"),
+        Ok(NaiveTime::from_hms_opt(9, 30, 0).unwrap())
+    );
+    assert_eq!(
+        NaiveDateTime::from_form_value("2024-02-29T09:30"),
+        Ok(date.and_hms_opt(9, 30, 0).unwrap())
+    );
+    assert_eq!(
+        NaiveDateTime::from_form_value("2024-02-29T09:30:15"),
+        Ok(date.and_hms_opt(9, 30, 15).unwrap())
+    );
+}
@@ -0,0 +1,141 @@
+#![cfg(feature = "http")]
+
+use http::{Method, Request, StatusCode};
+
+use crate::actions;
+
+fn post(uri: &str, body: &str) -> Request<String> {
+    Request::builder()
+        .method(Method::POST)
+        .uri(uri)
+        .header("Content-Type", "application/x-www-form-urlencoded")
+        .body(body.into())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn basic() {
+    #[actions(http)]
+    mod page {
+        use crate as html_form_actions;
+
+        use html_form_actions::http::{Request, Response, StatusCode};
+
+        #[action]
+        async fn my_action(#[form] a: i32, method: http::Method) -> String {
+            std::format!("{method} a = {a}")
+        }
+
+        #[action]
+        async fn created(#[form] name: String) -> (StatusCode, String) {
+            (StatusCode::CREATED, name)
+        }
+
+        pub async fn handle(request: Request<String>) -> Response<String> {
+            actions_handler(request).await
+        }
+    }
+
+    let response = page::handle(post("/basic?/my_action", "a=1")).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()[http::header::CONTENT_TYPE],
+        "text/plain; charset=utf-8"
+    );
+    assert_eq!(response.body(), "POST a = 1");
+
+    let response = page::handle(post("/basic?/created", "name=A+%26+B")).await;
+
+    assert_eq!(response.status(), StatusCode::CREATED);
+    assert_eq!(response.body(), "A & B");
+
+    let response = page::handle(post("/basic?/my_action", "a=one")).await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(response.body(), "Bad Form");
+
+    let response = page::handle(post("/basic?/missing", "")).await;
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(response.body(), "Action Not Found");
+}
+
+#[tokio::test]
+async fn fail() {
+    #[actions(http(handler = http_handler))]
+    mod page {
+        use crate as html_form_actions;
+
+        use html_form_actions::{
+            ActionResult, FieldError,
+            http::{Request, Response, StatusCode},
+        };
+
+        #[action(fail = my_action_failed)]
+        async fn my_action(#[form] a: i32) -> ActionResult<String, my_action::Errors> {
+            if a < 0 {
+                return Err(my_action::Errors {
+                    a: Some(FieldError::Message("A must not be negative")),
+                });
+            }
+
+            Ok(std::format!("a = {a}"))
+        }
+
+        async fn my_action_failed(failure: my_action::Failure<'_>) -> Response<String> {
+            let mut response = Response::new(std::format!(
+                "{}: {}",
+                failure.values.a.unwrap_or_default(),
+                failure.errors.a.unwrap()
+            ));
+
+            *response.status_mut() = StatusCode::UNPROCESSABLE_ENTITY;
+
+            response
+        }
+
+        pub async fn handle(request: Request<String>) -> Response<String> {
+            http_handler(request).await
+        }
+    }
+
+    let response = page::handle(post("/basic?/my_action", "a=2")).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.body(), "a = 2");
+
+    let response = page::handle(post("/basic?/my_action", "a=-2")).await;
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(response.body(), "-2: A must not be negative");
+}
+
+#[tokio::test]
+async fn limit() {
+    #[actions(http(limit = 8))]
+    mod page {
+        use crate as html_form_actions;
+
+        use html_form_actions::http::{Request, Response};
+
+        #[action]
+        async fn my_action(#[form] a: String) -> String {
+            a
+        }
+
+        pub async fn handle(request: Request<String>) -> Response<String> {
+            actions_handler(request).await
+        }
+    }
+
+    let response = page::handle(post("/basic?/my_action", "a=123456")).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.body(), "123456");
+
+    let response = page::handle(post("/basic?/my_action", "a=1234567")).await;
+
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(response.body(), "Payload Too Large");
+}
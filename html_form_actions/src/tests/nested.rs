@@ -0,0 +1,42 @@
+use crate::{FieldName, NamePrefix};
+
+#[test]
+fn names() {
+    let address = NamePrefix::Field {
+        parent: &NamePrefix::Root,
+        name: "address",
+    };
+
+    let item = NamePrefix::Index {
+        parent: &address,
+        name: "items",
+        index: 3,
+    };
+
+    assert_eq!(
+        FieldName {
+            prefix: NamePrefix::Root,
+            name: "street",
+        }
+        .to_string(),
+        "street"
+    );
+
+    assert_eq!(
+        FieldName {
+            prefix: address,
+            name: "street",
+        }
+        .to_string(),
+        "address.street"
+    );
+
+    assert_eq!(
+        FieldName {
+            prefix: item,
+            name: "qty",
+        }
+        .to_string(),
+        "address.items[3].qty"
+    );
+}
@@ -0,0 +1,169 @@
+use crate::{FieldError, actions};
+
+#[actions(parse)]
+mod page {
+    use crate as html_form_actions;
+
+    use html_form_actions::FieldError;
+
+    #[action]
+    fn add_item(#[form] name: String, #[form] quantity: Option<u32>) -> std::string::String {
+        std::format!("{name} {quantity:?}")
+    }
+
+    #[action]
+    fn r#clear() -> std::string::String {
+        "clear".into()
+    }
+
+    pub fn parse(
+        action_name: &str,
+        body: &str,
+    ) -> Result<std::string::String, std::string::String> {
+        let mut body = body.as_bytes().to_vec();
+
+        match ParsedAction::parse(action_name, &mut body) {
+            Ok(ParsedAction::AddItem { name, quantity }) => Ok(add_item(name, quantity)),
+            Ok(ParsedAction::Clear {}) => Ok(r#clear()),
+            Err(ParseError::AddItem(failure)) => Err(std::format!(
+                "{:?} {:?}",
+                failure.values.quantity,
+                failure.errors
+            )),
+            Err(error) => Err(std::format!("{error:?}")),
+        }
+    }
+
+    pub fn errors(quantity: Option<FieldError>) -> std::string::String {
+        std::format!(
+            "{:?}",
+            add_item::Errors {
+                name: None,
+                quantity
+            }
+        )
+    }
+}
+
+#[test]
+fn parse() {
+    assert_eq!(
+        page::parse("add_item", "name=Two+words&quantity=3"),
+        Ok("Two words Some(3)".into())
+    );
+    assert_eq!(page::parse("add_item", "name=A"), Ok("A None".into()));
+    assert_eq!(page::parse("clear", ""), Ok("clear".into()));
+}
+
+#[test]
+fn outside_module() {
+    let mut body = b"name=A&quantity=3".to_vec();
+
+    assert!(matches!(
+        page::ParsedAction::parse("add_item", &mut body),
+        Ok(page::ParsedAction::AddItem {
+            quantity: Some(3),
+            ..
+        })
+    ));
+    assert!(matches!(
+        page::ParsedAction::parse("remove_item", &mut body),
+        Err(page::ParseError::ActionNotFound)
+    ));
+
+    let mut body = b"name=A&quantity=-1".to_vec();
+
+    let Err(page::ParseError::AddItem(failure)) = page::ParsedAction::parse("add_item", &mut body)
+    else {
+        panic!("the quantity is invalid");
+    };
+
+    assert_eq!(failure.values.quantity, Some("-1"));
+    assert_eq!(
+        failure.errors,
+        page::add_item::Errors {
+            name: None,
+            quantity: Some(FieldError::Invalid),
+        }
+    );
+}
+
+#[test]
+fn raw_identifier() {
+    // The action is submitted and parsed by the same name
+    assert_eq!(page::r#clear::FORM.action, "?/clear");
+    assert!(matches!(
+        page::ParsedAction::parse("clear", &mut []),
+        Ok(page::ParsedAction::Clear {})
+    ));
+}
+
+#[test]
+fn failure() {
+    assert_eq!(
+        page::parse("add_item", "name=A&quantity=-1"),
+        Err(std::format!(
+            r#"Some("-1") {}"#,
+            page::errors(Some(FieldError::Invalid))
+        ))
+    );
+}
+
+#[test]
+fn errors() {
+    assert_eq!(
+        page::parse("remove_item", "name=A"),
+        Err("ActionNotFound".into())
+    );
+    assert!(
+        page::parse("add_item", "name=%ff")
+            .unwrap_err()
+            .starts_with("InvalidUtf8")
+    );
+}
+
+#[test]
+fn borrowed() {
+    #[actions(parse(action = Submitted, error = Rejected))]
+    mod page {
+        use std::borrow::Cow;
+
+        use crate as html_form_actions;
+
+        #[action]
+        fn rename(
+            #[form] name: &str,
+            #[form] note: Option<&'_ str>,
+            #[form] tags: Vec<&str>,
+            #[form] title: Cow<str>,
+        ) -> std::string::String {
+            std::format!("{name} {note:?} {tags:?} {title}")
+        }
+
+        /// The result of the action, the parsed name, and whether the title is borrowed.
+        pub fn parse(body: &mut [u8]) -> (std::string::String, &str, bool) {
+            let Ok(Submitted::Rename {
+                name,
+                note,
+                tags,
+                title,
+            }) = Submitted::parse("rename", body)
+            else {
+                panic!("the submission is valid");
+            };
+
+            let borrowed = matches!(title, Cow::Borrowed(_));
+
+            (rename(name, note, tags, title), name, borrowed)
+        }
+    }
+
+    let mut body = b"name=Two+words&tags=a&tags=b%26c&title=T%C3%AEtle".to_vec();
+    let body_range = body.as_ptr_range();
+
+    let (result, name, borrowed) = page::parse(&mut body);
+
+    assert_eq!(result, r#"Two words None ["a", "b&c"] Tîtle"#);
+    assert!(body_range.contains(&name.as_ptr()));
+    assert!(borrowed);
+}
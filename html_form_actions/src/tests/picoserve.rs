@@ -1,15 +1,24 @@
+#![cfg(feature = "testing-picoserve")]
+
 use crate::{BuildExt, actions};
-#[test]
-fn basic() {
+
+#[tokio::test]
+async fn basic() {
+    use crate::testing::picoserve::Client;
+
     #[actions(picoserve)]
     mod page {
-        const PATH: &str = "/basic";
+        use crate as html_form_actions;
+
+        use html_form_actions::testing::picoserve::{Client, TestResponse};
+
+        pub const PATH: &str = "/basic";
 
         async fn page_handler() -> impl picoserve::response::IntoResponse {
             (
                 ("Content-Type", "text/html; charset=utf-8"),
                 maud::html! {
-                    @let my_action::Form { action, a_name } = my_action::FORM;
+                    @let my_action::Form { action, a_name, .. } = my_action::FORM;
                     form action=(action) {
                         label { "A" input name=(a_name); }
                     }
@@ -31,22 +40,53 @@ fn basic() {
                 picoserve::routing::get(page_handler).post_service(ActionsHandler),
             )
         }
+
+        pub async fn submit<P: picoserve::routing::PathRouter>(
+            client: &Client<'_, P>,
+            a: i32,
+        ) -> TestResponse {
+            client
+                .submit(my_action::typed_submission(&my_action::Typed { a }))
+                .await
+        }
+
+        pub async fn submit_raw<P: picoserve::routing::PathRouter>(
+            client: &Client<'_, P>,
+            a: &'static str,
+        ) -> TestResponse {
+            client
+                .submit_raw::<my_action::Action>(my_action::Values { a: Some(a) })
+                .await
+        }
     }
 
-    picoserve::Router::new().with(page::route);
+    let app = picoserve::Router::new().with(page::route);
+    let client = Client::new(&app, page::PATH);
+
+    let response = page::submit(&client, 1).await;
+
+    assert_eq!((response.status, response.body.as_str()), (200, "a = 1"));
+
+    assert_eq!(page::submit_raw(&client, "one").await.status, 400);
 }
 
-#[test]
-fn named_handler() {
+#[tokio::test]
+async fn named_handler() {
+    use crate::testing::picoserve::Client;
+
     #[actions(picoserve(handler = NamedActionsHandler))]
     mod page {
-        const PATH: &str = "/basic";
+        use crate as html_form_actions;
+
+        use html_form_actions::testing::picoserve::{Client, TestResponse};
+
+        pub const PATH: &str = "/basic";
 
         async fn page_handler() -> impl picoserve::response::IntoResponse {
             (
                 ("Content-Type", "text/html; charset=utf-8"),
                 maud::html! {
-                    @let my_action::Form { action, a_name } = my_action::FORM;
+                    @let my_action::Form { action, a_name, .. } = my_action::FORM;
                     form action=(action) {
                         label { "A" input name=(a_name); }
                     }
@@ -68,7 +108,534 @@ fn named_handler() {
                 picoserve::routing::get(page_handler).post_service(NamedActionsHandler),
             )
         }
+
+        pub async fn submit<P: picoserve::routing::PathRouter>(
+            client: &Client<'_, P>,
+            a: &'static str,
+        ) -> TestResponse {
+            client
+                .submit_raw::<my_action::Action>(my_action::Values { a: Some(a) })
+                .await
+        }
+    }
+
+    let app = picoserve::Router::new().with(page::route);
+    let client = Client::new(&app, page::PATH);
+
+    let response = page::submit(&client, "2").await;
+
+    assert_eq!((response.status, response.body.as_str()), (200, "a = 2"));
+}
+
+#[tokio::test]
+async fn fail() {
+    use crate::testing::picoserve::Client;
+
+    #[actions(picoserve)]
+    mod page {
+        use crate as html_form_actions;
+
+        use html_form_actions::{
+            ActionResult, FieldError,
+            testing::picoserve::{Client, TestResponse},
+        };
+
+        pub const PATH: &str = "/basic";
+
+        fn render(failure: Option<&my_action::Failure>) -> std::string::String {
+            let values = failure.map(|failure| failure.values).unwrap_or_default();
+            let errors = failure.map(|failure| failure.errors).unwrap_or_default();
+
+            maud::html! {
+                @let my_action::Form { action, a_name, .. } = my_action::FORM;
+                form action=(action) {
+                    label { "A" input name=(a_name) value=[values.a]; }
+                    @if let Some(error) = errors.a {
+                        p { (error) }
+                    }
+                }
+            }
+            .into_string()
+        }
+
+        async fn page_handler() -> impl picoserve::response::IntoResponse {
+            (("Content-Type", "text/html; charset=utf-8"), render(None))
+        }
+
+        #[action(fail = my_action_failed)]
+        async fn my_action(#[form] a: i32) -> ActionResult<std::string::String, my_action::Errors> {
+            if a < 0 {
+                return Err(my_action::Errors {
+                    a: Some(FieldError::Message("A must not be negative")),
+                });
+            }
+
+            Ok(std::format!("a = {a}"))
+        }
+
+        async fn my_action_failed(
+            failure: my_action::Failure<'_>,
+        ) -> impl picoserve::response::IntoResponse + use<> {
+            (
+                picoserve::response::StatusCode::UNPROCESSABLE_ENTITY,
+                ("Content-Type", "text/html; charset=utf-8"),
+                render(Some(&failure)),
+            )
+        }
+
+        pub fn route<R: picoserve::routing::PathRouter>(
+            router: picoserve::Router<R>,
+        ) -> picoserve::Router<impl picoserve::routing::PathRouter> {
+            router.route(
+                PATH,
+                picoserve::routing::get(page_handler).post_service(ActionsHandler),
+            )
+        }
+
+        pub async fn submit<P: picoserve::routing::PathRouter>(
+            client: &Client<'_, P>,
+            a: &'static str,
+        ) -> TestResponse {
+            client
+                .submit_raw::<my_action::Action>(my_action::Values { a: Some(a) })
+                .await
+        }
+    }
+
+    let app = picoserve::Router::new().with(page::route);
+    let client = Client::new(&app, page::PATH);
+
+    let response = page::submit(&client, "3").await;
+
+    assert_eq!((response.status, response.body.as_str()), (200, "a = 3"));
+
+    let response = page::submit(&client, "-1").await;
+
+    assert_eq!(response.status, 422);
+    assert!(response.body.contains(r#"value="-1""#));
+    assert!(response.body.contains("<p>A must not be negative</p>"));
+}
+
+#[tokio::test]
+async fn reject() {
+    use crate::testing::picoserve::Client;
+
+    #[actions(picoserve, reject = rejected)]
+    mod page {
+        use crate as html_form_actions;
+
+        use html_form_actions::{
+            FieldError, Rejection,
+            testing::picoserve::{Client, TestResponse},
+        };
+
+        pub const PATH: &str = "/basic";
+
+        #[action]
+        async fn my_action(#[form] a: i32, #[form] b: Result<u8, FieldError>) -> String {
+            std::format!("a = {a}, b = {b:?}")
+        }
+
+        async fn rejected(rejection: Rejection<'_>) -> (picoserve::response::StatusCode, String) {
+            (
+                picoserve::response::StatusCode::BAD_REQUEST,
+                rejection.to_string(),
+            )
+        }
+
+        pub fn route<R: picoserve::routing::PathRouter>(
+            router: picoserve::Router<R>,
+        ) -> picoserve::Router<impl picoserve::routing::PathRouter> {
+            router.route(PATH, picoserve::routing::post_service(ActionsHandler))
+        }
+
+        pub async fn submit<P: picoserve::routing::PathRouter>(
+            client: &Client<'_, P>,
+            a: Option<&'static str>,
+            b: Option<&'static str>,
+        ) -> TestResponse {
+            client
+                .submit_raw::<my_action::Action>(my_action::Values { a, b })
+                .await
+        }
+    }
+
+    let app = picoserve::Router::new().with(page::route);
+    let client = Client::new(&app, page::PATH);
+
+    let response = page::submit(&client, Some("1"), Some("256")).await;
+
+    assert_eq!(
+        (response.status, response.body.as_str()),
+        (200, "a = 1, b = Err(Invalid)")
+    );
+
+    let response = page::submit(&client, Some("one"), None).await;
+
+    assert_eq!(
+        (response.status, response.body.as_str()),
+        (400, "a: This value is invalid")
+    );
+}
+
+#[tokio::test]
+async fn checkbox_and_optional() {
+    use crate::testing::picoserve::Client;
+
+    #[actions(picoserve)]
+    mod page {
+        use crate as html_form_actions;
+
+        use html_form_actions::testing::picoserve::{Client, TestResponse};
+
+        pub const PATH: &str = "/basic";
+
+        async fn page_handler() -> impl picoserve::response::IntoResponse {
+            (
+                ("Content-Type", "text/html; charset=utf-8"),
+                maud::html! {
+                    @let my_action::Form { action, flag_name, flag_input_type, note_name, .. } = my_action::FORM;
+                    form action=(action) {
+                        input type=(flag_input_type) name=(flag_name);
+                        input name=(note_name);
+                    }
+                }
+                .into_string(),
+            )
+        }
+
+        #[action]
+        async fn my_action(#[form] flag: bool, #[form] note: Option<i32>) -> String {
+            std::format!("{flag} {note:?}")
+        }
+
+        pub fn route<R: picoserve::routing::PathRouter>(
+            router: picoserve::Router<R>,
+        ) -> picoserve::Router<impl picoserve::routing::PathRouter> {
+            router.route(
+                PATH,
+                picoserve::routing::get(page_handler).post_service(ActionsHandler),
+            )
+        }
+
+        pub async fn submit<P: picoserve::routing::PathRouter>(
+            client: &Client<'_, P>,
+            flag: Option<&'static str>,
+            note: Option<&'static str>,
+        ) -> TestResponse {
+            client
+                .submit_raw::<my_action::Action>(my_action::Values { flag, note })
+                .await
+        }
+    }
+
+    let app = picoserve::Router::new().with(page::route);
+    let client = Client::new(&app, page::PATH);
+
+    // An unchecked checkbox is not submitted
+    let response = page::submit(&client, None, None).await;
+
+    assert_eq!(
+        (response.status, response.body.as_str()),
+        (200, "false None")
+    );
+
+    // An empty optional field is `None`
+    let response = page::submit(&client, Some("on"), Some("")).await;
+
+    assert_eq!(
+        (response.status, response.body.as_str()),
+        (200, "true None")
+    );
+
+    let response = page::submit(&client, Some("on"), Some("3")).await;
+
+    assert_eq!(
+        (response.status, response.body.as_str()),
+        (200, "true Some(3)")
+    );
+
+    assert_eq!(page::submit(&client, None, Some("three")).await.status, 400);
+}
+
+#[cfg(feature = "heapless")]
+#[tokio::test]
+async fn multiple() {
+    use crate::testing::picoserve::Client;
+
+    #[actions(picoserve, reject = rejected)]
+    mod page {
+        use crate as html_form_actions;
+
+        use html_form_actions::{
+            Rejection,
+            testing::picoserve::{Client, TestResponse},
+        };
+
+        pub const PATH: &str = "/basic";
+
+        async fn page_handler() -> impl picoserve::response::IntoResponse {
+            (
+                ("Content-Type", "text/html; charset=utf-8"),
+                maud::html! {
+                    @let my_action::Form { action, tags_name, tags_multiple, name_name, name_max_length, .. } = my_action::FORM;
+                    form action=(action) {
+                        select name=(tags_name) multiple[tags_multiple] {
+                            option { "a" }
+                            option { "b" }
+                        }
+                        input name=(name_name) maxlength=[name_max_length];
+                    }
+                }
+                .into_string(),
+            )
+        }
+
+        #[action]
+        async fn my_action(
+            #[form] tags: heapless::Vec<char, 2>,
+            #[form] name: heapless::String<16>,
+        ) -> String {
+            std::format!("{name}: {tags:?}")
+        }
+
+        async fn rejected(
+            rejection: Rejection<'_>,
+        ) -> impl picoserve::response::IntoResponse + use<> {
+            (
+                picoserve::response::StatusCode::BAD_REQUEST,
+                std::format!("{rejection}"),
+            )
+        }
+
+        pub fn route<R: picoserve::routing::PathRouter>(
+            router: picoserve::Router<R>,
+        ) -> picoserve::Router<impl picoserve::routing::PathRouter> {
+            router.route(
+                PATH,
+                picoserve::routing::get(page_handler).post_service(ActionsHandler),
+            )
+        }
+
+        pub async fn submit<P: picoserve::routing::PathRouter>(
+            client: &Client<'_, P>,
+            tags: &[&'static str],
+            name: &'static str,
+        ) -> TestResponse {
+            client
+                .submit_raw::<my_action::Action>(my_action::Values {
+                    tags: tags.iter().copied().collect(),
+                    name: Some(name),
+                })
+                .await
+        }
+    }
+
+    let app = picoserve::Router::new().with(page::route);
+    let client = Client::new(&app, page::PATH);
+
+    let response = page::submit(&client, &["a", "b"], "Ann").await;
+
+    assert_eq!(
+        (response.status, response.body.as_str()),
+        (200, "Ann: ['a', 'b']")
+    );
+
+    let response = page::submit(&client, &[], "Ann").await;
+
+    assert_eq!((response.status, response.body.as_str()), (200, "Ann: []"));
+
+    let response = page::submit(&client, &["a", "bc"], "Ann").await;
+
+    assert_eq!(
+        (response.status, response.body.as_str()),
+        (400, "tags: This value is invalid")
+    );
+
+    let response = page::submit(&client, &["a"], "seventeen bytes!!").await;
+
+    assert_eq!(response.status, 400);
+    assert!(response.body.starts_with("name: "));
+}
+
+#[tokio::test]
+async fn flatten() {
+    use crate::testing::picoserve::Client;
+
+    #[actions(picoserve)]
+    mod page {
+        use crate as html_form_actions;
+
+        use html_form_actions::{
+            HtmlForm,
+            testing::picoserve::{Client, TestResponse},
+        };
+
+        pub const PATH: &str = "/basic";
+
+        #[derive(HtmlForm)]
+        struct ProfileForm {
+            #[form(required)]
+            name: String,
+            age: u32,
+        }
+
+        async fn page_handler() -> impl picoserve::response::IntoResponse {
+            (
+                ("Content-Type", "text/html; charset=utf-8"),
+                maud::html! {
+                    @let my_action::Form { action, profile, .. } = my_action::FORM;
+                    @let profile_form::Form { name_name, age_name, .. } = profile;
+                    form action=(action) {
+                        input name=(name_name);
+                        input name=(age_name);
+                    }
+                }
+                .into_string(),
+            )
+        }
+
+        #[action]
+        async fn my_action(#[form(flatten)] profile: ProfileForm) -> String {
+            std::format!("{} {}", profile.name, profile.age)
+        }
+
+        pub fn route<R: picoserve::routing::PathRouter>(
+            router: picoserve::Router<R>,
+        ) -> picoserve::Router<impl picoserve::routing::PathRouter> {
+            router.route(
+                PATH,
+                picoserve::routing::get(page_handler).post_service(ActionsHandler),
+            )
+        }
+
+        pub async fn submit<P: picoserve::routing::PathRouter>(
+            client: &Client<'_, P>,
+            name: Option<&'static str>,
+            age: &'static str,
+        ) -> TestResponse {
+            client
+                .submit_raw::<my_action::Action>(my_action::Values {
+                    profile: profile_form::Values {
+                        name,
+                        age: Some(age),
+                    },
+                })
+                .await
+        }
+    }
+
+    let app = picoserve::Router::new().with(page::route);
+    let client = Client::new(&app, page::PATH);
+
+    let response = page::submit(&client, Some("Ann"), "30").await;
+
+    assert_eq!((response.status, response.body.as_str()), (200, "Ann 30"));
+
+    assert_eq!(page::submit(&client, None, "30").await.status, 400);
+    assert_eq!(
+        page::submit(&client, Some("Ann"), "thirty").await.status,
+        400
+    );
+}
+
+#[tokio::test]
+async fn client() {
+    use crate::testing::picoserve::{Client, TestResponse};
+
+    #[actions(picoserve)]
+    mod page {
+        use crate as html_form_actions;
+
+        use html_form_actions::testing::picoserve::{Client, TestResponse};
+
+        pub const PATH: &str = "/basic";
+
+        #[action]
+        async fn my_action(#[form] a: i32, #[form] b: Option<String>) -> String {
+            std::format!("a = {a}, b = {b:?}")
+        }
+
+        pub fn route<R: picoserve::routing::PathRouter>(
+            router: picoserve::Router<R>,
+        ) -> picoserve::Router<impl picoserve::routing::PathRouter> {
+            router.route(PATH, picoserve::routing::post_service(ActionsHandler))
+        }
+
+        pub async fn submit<P: picoserve::routing::PathRouter>(
+            client: &Client<'_, P>,
+            a: &'static str,
+        ) -> TestResponse {
+            client
+                .submit_raw::<my_action::Action>(my_action::Values {
+                    a: Some(a),
+                    b: Some("x & y"),
+                })
+                .await
+        }
+    }
+
+    let app = picoserve::Router::new().with(page::route);
+    let client = Client::new(&app, page::PATH);
+
+    let response = page::submit(&client, "1").await;
+
+    assert_eq!(response.status, 200);
+    assert_eq!(
+        response.header("content-type"),
+        Some("text/plain; charset=utf-8")
+    );
+
+    let TestResponse { body, .. } = response;
+
+    assert_eq!(body, r#"a = 1, b = Some("x & y")"#);
+
+    assert_eq!(page::submit(&client, "one").await.status, 400);
+}
+
+#[tokio::test]
+async fn borrowed() {
+    use crate::testing::picoserve::Client;
+
+    #[actions(picoserve)]
+    mod page {
+        use crate as html_form_actions;
+
+        use html_form_actions::testing::picoserve::{Client, TestResponse};
+
+        pub const PATH: &str = "/borrowed";
+
+        #[action]
+        async fn greet(#[form] name: &str, #[form] greeting: Option<&str>) -> String {
+            std::format!("{}, {name}!", greeting.unwrap_or("Hello"))
+        }
+
+        pub fn route<R: picoserve::routing::PathRouter>(
+            router: picoserve::Router<R>,
+        ) -> picoserve::Router<impl picoserve::routing::PathRouter> {
+            router.route(PATH, picoserve::routing::post_service(ActionsHandler))
+        }
+
+        pub async fn submit<P: picoserve::routing::PathRouter>(
+            client: &Client<'_, P>,
+            name: Option<&'static str>,
+            greeting: Option<&'static str>,
+        ) -> TestResponse {
+            client
+                .submit_raw::<greet::Action>(greet::Values { name, greeting })
+                .await
+        }
     }
 
-    picoserve::Router::new().with(page::route);
+    let app = picoserve::Router::new().with(page::route);
+    let client = Client::new(&app, page::PATH);
+
+    let response = page::submit(&client, Some("A & B"), None).await;
+
+    assert_eq!(
+        (response.status, response.body.as_str()),
+        (200, "Hello, A & B!")
+    );
+
+    assert_eq!(page::submit(&client, None, Some("Hi")).await.status, 400);
 }
@@ -0,0 +1,184 @@
+use poem::{Endpoint, Request, http::StatusCode};
+
+use crate::actions;
+
+async fn post(endpoint: impl Endpoint, uri: &str, body: &'static str) -> (StatusCode, String) {
+    let request = Request::builder()
+        .method(poem::http::Method::POST)
+        .uri(uri.parse().unwrap())
+        .content_type("application/x-www-form-urlencoded")
+        .body(body);
+
+    let response = endpoint.get_response(request).await;
+
+    let status = response.status();
+
+    (
+        status,
+        response.into_body().into_string().await.unwrap_or_default(),
+    )
+}
+
+#[tokio::test]
+async fn basic() {
+    #[actions(poem)]
+    mod page {
+        use poem::{EndpointExt, http::Method, web::Data};
+
+        use crate as html_form_actions;
+
+        #[derive(Clone)]
+        pub struct AppState {
+            pub offset: i32,
+        }
+
+        #[action]
+        async fn my_action(#[form] a: i32, Data(state): Data<&AppState>) -> String {
+            std::format!("a = {}", a + state.offset)
+        }
+
+        #[action]
+        async fn method(#[form] name: String, method: Method) -> String {
+            std::format!("{method} {name}")
+        }
+
+        pub fn endpoint() -> impl poem::Endpoint {
+            poem::Route::new().at(
+                "/basic",
+                poem::post(ActionsEndpoint).data(AppState { offset: 10 }),
+            )
+        }
+    }
+
+    assert_eq!(
+        post(page::endpoint(), "/basic?/my_action", "a=1").await,
+        (StatusCode::OK, "a = 11".into())
+    );
+    assert_eq!(
+        post(page::endpoint(), "/basic?/method", "name=A+%26+B").await,
+        (StatusCode::OK, "POST A & B".into())
+    );
+    assert_eq!(
+        post(page::endpoint(), "/basic?/my_action", "a=one").await,
+        (StatusCode::BAD_REQUEST, "Bad Form".into())
+    );
+    assert_eq!(
+        post(page::endpoint(), "/basic?/missing", "a=1").await,
+        (StatusCode::NOT_FOUND, "Action Not Found".into())
+    );
+}
+
+#[tokio::test]
+async fn missing_data() {
+    #[actions(poem(handler = NamedActionsEndpoint))]
+    mod page {
+        use poem::web::Data;
+
+        use crate as html_form_actions;
+
+        #[action]
+        async fn my_action(#[form] a: i32, Data(state): Data<&u8>) -> String {
+            std::format!("a = {a}, state = {state}")
+        }
+
+        pub fn endpoint() -> impl poem::Endpoint {
+            NamedActionsEndpoint
+        }
+    }
+
+    assert_eq!(
+        post(page::endpoint(), "/basic?/my_action", "a=1").await.0,
+        StatusCode::INTERNAL_SERVER_ERROR
+    );
+}
+
+#[tokio::test]
+async fn fail() {
+    #[actions(poem, reject = rejected)]
+    mod page {
+        use poem::http::StatusCode;
+
+        use crate as html_form_actions;
+
+        use html_form_actions::{ActionResult, FieldError, Rejection};
+
+        #[action(fail = my_action_failed)]
+        async fn my_action(#[form] a: i32) -> ActionResult<String, my_action::Errors> {
+            if a < 0 {
+                return Err(my_action::Errors {
+                    a: Some(FieldError::Message("A must not be negative")),
+                });
+            }
+
+            Ok(std::format!("a = {a}"))
+        }
+
+        async fn my_action_failed(failure: my_action::Failure<'_>) -> (StatusCode, String) {
+            (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                std::format!(
+                    "{}: {}",
+                    failure.values.a.unwrap_or_default(),
+                    failure.errors.a.unwrap()
+                ),
+            )
+        }
+
+        #[action]
+        async fn other_action(#[form] b: u8) -> String {
+            std::format!("b = {b}")
+        }
+
+        async fn rejected(rejection: Rejection<'_>) -> (StatusCode, String) {
+            (StatusCode::BAD_REQUEST, rejection.to_string())
+        }
+
+        pub fn endpoint() -> impl poem::Endpoint {
+            ActionsEndpoint
+        }
+    }
+
+    assert_eq!(
+        post(page::endpoint(), "/basic?/my_action", "a=1").await,
+        (StatusCode::OK, "a = 1".into())
+    );
+    assert_eq!(
+        post(page::endpoint(), "/basic?/my_action", "a=-1").await,
+        (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "-1: A must not be negative".into()
+        )
+    );
+    assert_eq!(
+        post(page::endpoint(), "/basic?/other_action", "b=300").await,
+        (StatusCode::BAD_REQUEST, "b: This value is invalid".into())
+    );
+}
+
+#[tokio::test]
+async fn limit() {
+    #[actions(poem(limit = 8))]
+    mod page {
+        use crate as html_form_actions;
+
+        #[action]
+        async fn my_action(#[form] a: i32) -> String {
+            std::format!("a = {a}")
+        }
+
+        pub fn endpoint() -> impl poem::Endpoint {
+            poem::Route::new().at("/basic", poem::post(ActionsEndpoint))
+        }
+    }
+
+    assert_eq!(
+        post(page::endpoint(), "/basic?/my_action", "a=123456").await,
+        (StatusCode::OK, "a = 123456".into())
+    );
+    assert_eq!(
+        post(page::endpoint(), "/basic?/my_action", "a=1234567")
+            .await
+            .0,
+        StatusCode::PAYLOAD_TOO_LARGE
+    );
+}
@@ -0,0 +1,143 @@
+use crate::{BuildExt, FormValue, FromFormValue, ToFormValue, actions};
+
+fn round_trip<T: ToFormValue + for<'r> FromFormValue<'r> + PartialEq + core::fmt::Debug>(value: T) {
+    let formatted = FormValue::from(&value).to_string();
+
+    assert_eq!(T::from_form_value(&formatted), Ok(value));
+}
+
+#[test]
+fn form_values() {
+    round_trip(-3_i32);
+    round_trip(0.5_f64);
+    round_trip(true);
+    round_trip(false);
+    round_trip('x');
+    round_trip(String::from("text"));
+
+    assert_eq!(FormValue::Raw("a").to_string(), "a");
+    assert_eq!(FormValue::from(&42_u8).to_string(), "42");
+    assert_eq!(
+        FormValue::Raw(r#"<"a">"#).value_attribute().to_string(),
+        r#" value="&lt;&quot;a&quot;&gt;""#
+    );
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn chrono() {
+    round_trip(chrono::NaiveDate::from_ymd_opt(2024, 2, 29).unwrap());
+    round_trip(chrono::NaiveTime::from_hms_opt(9, 5, 0).unwrap());
+    round_trip(
+        chrono::NaiveDate::from_ymd_opt(2024, 2, 29)
+            .unwrap()
+            .and_hms_opt(9, 5, 30)
+            .unwrap(),
+    );
+
+    assert_eq!(
+        FormValue::from(&chrono::NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()).to_string(),
+        "2024-02-29"
+    );
+}
+
+#[test]
+fn prefill() {
+    #[actions(axum)]
+    mod page {
+        use axum::routing::get;
+
+        use crate as html_form_actions;
+
+        use html_form_actions::{FormOptions, HtmlForm};
+
+        const PATH: &str = "/basic";
+
+        #[derive(FormOptions)]
+        pub enum Size {
+            Small,
+            Large,
+        }
+
+        #[derive(HtmlForm)]
+        pub struct Address {
+            #[form]
+            pub street: String,
+        }
+
+        pub struct Item {
+            pub count: u32,
+            pub size: Size,
+            pub street: String,
+        }
+
+        async fn page_handler() -> maud::PreEscaped<String> {
+            render(None, None)
+        }
+
+        pub fn render(item: Option<&Item>, failure: Option<&str>) -> maud::PreEscaped<String> {
+            let values = my_action::Values {
+                count: failure,
+                ..Default::default()
+            };
+
+            let prefill = match item {
+                Some(item) => my_action::Prefill {
+                    count: Some((&item.count).into()),
+                    size: Some((&item.size).into()),
+                    address: address::Prefill {
+                        street: Some((&item.street).into()),
+                    },
+                },
+                None => my_action::Prefill::from(&values),
+            };
+
+            maud::html! {
+                @let my_action::Form { action, count_name, size_name, address, .. } = my_action::FORM;
+                form action=(action) {
+                    input name=(count_name) value=[prefill.count];
+                    input name=(size_name) value=[prefill.size];
+                    input name=(address.street_name) value=[prefill.address.street];
+                }
+            }
+        }
+
+        #[action]
+        async fn my_action(
+            #[form] count: u32,
+            #[form] size: Size,
+            #[form(flatten)] address: Address,
+        ) -> String {
+            std::format!("{count} {} {}", size.value(), address.street)
+        }
+
+        pub fn route(router: axum::Router) -> axum::Router {
+            router.route(PATH, get(page_handler).post(actions_handler))
+        }
+    }
+
+    let _ = axum::Router::new().with(page::route).into_make_service();
+
+    assert_eq!(
+        page::render(None, None).into_string(),
+        r#"<form action="?/my_action"><input name="count"><input name="size"><input name="street"></form>"#
+    );
+
+    assert_eq!(
+        page::render(None, Some("<3")).into_string(),
+        r#"<form action="?/my_action"><input name="count" value="&lt;3"><input name="size"><input name="street"></form>"#
+    );
+
+    assert_eq!(
+        page::render(
+            Some(&page::Item {
+                count: 3,
+                size: page::Size::Large,
+                street: "High Street".into(),
+            }),
+            None
+        )
+        .into_string(),
+        r#"<form action="?/my_action"><input name="count" value="3"><input name="size" value="large"><input name="street" value="High Street"></form>"#
+    );
+}
@@ -0,0 +1,111 @@
+use crate::{BuildExt, actions};
+
+#[test]
+fn render() {
+    #[actions(axum)]
+    mod page {
+        use crate as html_form_actions;
+
+        use html_form_actions::{FieldError, FormOptions, HtmlForm};
+
+        #[derive(FormOptions)]
+        pub enum Size {
+            Small,
+            Large,
+        }
+
+        #[derive(HtmlForm)]
+        pub struct Row {
+            #[form]
+            pub value: i32,
+        }
+
+        #[action]
+        async fn my_action(
+            #[form(
+                required,
+                min_length = 1,
+                placeholder = "Jane",
+                help = "Your \"full\" name"
+            )]
+            name: String,
+            #[form(min = 0)] count: u32,
+            #[form] subscribe: bool,
+            #[form] size: Size,
+            #[form(input_type = "hidden")] index: usize,
+            #[form(nested)] rows: Vec<Row>,
+        ) -> String {
+            let rows = rows.iter().map(|row| row.value).sum::<i32>();
+
+            std::format!("{name} {count} {subscribe} {} {index} {rows}", size.value())
+        }
+
+        pub fn route(router: axum::Router) -> axum::Router {
+            router.route("/", axum::routing::post(actions_handler))
+        }
+
+        pub fn empty() -> String {
+            my_action::render(&Default::default(), &Default::default()).to_string()
+        }
+
+        #[cfg(feature = "maud")]
+        pub fn markup() -> maud::Markup {
+            maud::html! {
+                (my_action::render(&Default::default(), &Default::default()))
+            }
+        }
+
+        pub fn prefilled() -> String {
+            let values = my_action::Values {
+                name: Some("<b>"),
+                count: Some("-1"),
+                subscribe: Some("on"),
+                size: Some("large"),
+                index: Some("3"),
+                rows: vec![(0, row::Values { value: Some("x") })],
+            };
+
+            let errors = my_action::Errors {
+                count: Some(FieldError::RangeUnderflow { min: "0" }),
+                rows: vec![row::Errors {
+                    value: Some(FieldError::Invalid),
+                }],
+                ..Default::default()
+            };
+
+            my_action::render(&values, &errors).to_string()
+        }
+    }
+
+    let _ = axum::Router::new().with(page::route).into_make_service();
+
+    assert_eq!(
+        page::empty(),
+        concat!(
+            r#"<form method="post" action="?/my_action">"#,
+            r#"<div><label for="page-my_action-name">Name</label><input type="text" name="name" required placeholder="Jane" minlength="1" id="page-my_action-name"><small>Your &quot;full&quot; name</small></div>"#,
+            r#"<div><label for="page-my_action-count">Count</label><input type="number" name="count" min="0" step="1" id="page-my_action-count"></div>"#,
+            r#"<div><label for="page-my_action-subscribe">Subscribe</label><input type="checkbox" name="subscribe" id="page-my_action-subscribe"></div>"#,
+            r#"<div><fieldset><legend>Size</legend><label><input type="radio" name="size" value="small">Small</label><label><input type="radio" name="size" value="large">Large</label></fieldset></div>"#,
+            r#"<input type="hidden" name="index" step="1">"#,
+            r#"<button type="submit">My action</button></form>"#,
+        )
+    );
+
+    assert_eq!(
+        page::prefilled(),
+        concat!(
+            r#"<form method="post" action="?/my_action">"#,
+            r#"<div><label for="page-my_action-name">Name</label><input type="text" name="name" value="&lt;b&gt;" required placeholder="Jane" minlength="1" id="page-my_action-name"><small>Your &quot;full&quot; name</small></div>"#,
+            r#"<div><label for="page-my_action-count">Count</label><input type="number" name="count" value="-1" min="0" step="1" aria-invalid="true" aria-describedby="page-my_action-count-error" id="page-my_action-count"><p id="page-my_action-count-error">This value must be at least 0</p></div>"#,
+            r#"<div><label for="page-my_action-subscribe">Subscribe</label><input type="checkbox" name="subscribe" id="page-my_action-subscribe" checked></div>"#,
+            r#"<div><fieldset><legend>Size</legend><label><input type="radio" name="size" value="small">Small</label><label><input type="radio" name="size" value="large" checked>Large</label></fieldset></div>"#,
+            r#"<input type="hidden" name="index" value="3" step="1">"#,
+            r#"<fieldset><div><label for="page-my_action-rows-0-value">Value</label><input type="number" name="rows[0].value" value="x" step="1" aria-invalid="true" aria-describedby="page-my_action-rows-0-value-error" id="page-my_action-rows-0-value"><p id="page-my_action-rows-0-value-error">This value is invalid</p></div></fieldset>"#,
+            r#"<button type="submit">My action</button></form>"#,
+        )
+    );
+
+    #[cfg(feature = "maud")]
+    assert_eq!(page::markup().into_string(), page::empty());
+}
@@ -0,0 +1,271 @@
+use rocket::{
+    Build, Rocket,
+    http::{ContentType, Status},
+    local::asynchronous::Client,
+};
+
+use crate::actions;
+
+async fn post(rocket: Rocket<Build>, uri: &str, body: &'static str) -> (Status, String) {
+    let client = Client::tracked(rocket).await.unwrap();
+
+    let response = client
+        .post(uri.to_owned())
+        .header(ContentType::Form)
+        .body(body)
+        .dispatch()
+        .await;
+
+    let status = response.status();
+
+    (status, response.into_string().await.unwrap_or_default())
+}
+
+fn rocket() -> Rocket<Build> {
+    rocket::custom(rocket::Config {
+        log_level: rocket::config::LogLevel::Off,
+        ..rocket::Config::debug_default()
+    })
+}
+
+#[rocket::async_test]
+async fn basic() {
+    #[actions(rocket)]
+    mod page {
+        use rocket::{Build, Rocket, State, http::Method};
+
+        use crate as html_form_actions;
+
+        pub struct AppState {
+            pub offset: i32,
+        }
+
+        #[action]
+        async fn my_action(#[form] a: i32, state: &State<AppState>) -> String {
+            std::format!("a = {}", a + state.offset)
+        }
+
+        #[action]
+        async fn method(#[form] name: String, method: Method) -> String {
+            std::format!("{method} {name}")
+        }
+
+        pub fn mount(rocket: Rocket<Build>) -> Rocket<Build> {
+            rocket
+                .manage(AppState { offset: 10 })
+                .mount("/basic", ActionsHandler)
+        }
+    }
+
+    let app = || page::mount(rocket());
+
+    assert_eq!(
+        post(app(), "/basic?/my_action", "a=1").await,
+        (Status::Ok, "a = 11".into())
+    );
+    assert_eq!(
+        post(app(), "/basic?/method", "name=A+%26+B").await,
+        (Status::Ok, "POST A & B".into())
+    );
+    assert_eq!(
+        post(app(), "/basic?/my_action", "a=one").await,
+        (Status::BadRequest, "Bad Form".into())
+    );
+    assert_eq!(
+        post(app(), "/basic?/missing", "a=1").await,
+        (Status::NotFound, "Action Not Found".into())
+    );
+}
+
+#[rocket::async_test]
+async fn guard_failure() {
+    #[actions(rocket(handler = NamedActionsHandler))]
+    mod page {
+        use rocket::{Build, Rocket, State};
+
+        use crate as html_form_actions;
+
+        #[action]
+        async fn my_action(#[form] a: i32, state: &State<u8>) -> String {
+            std::format!("a = {a}, state = {state}")
+        }
+
+        pub fn mount(rocket: Rocket<Build>) -> Rocket<Build> {
+            rocket.mount("/basic", NamedActionsHandler)
+        }
+    }
+
+    assert_eq!(
+        post(page::mount(rocket()), "/basic?/my_action", "a=1")
+            .await
+            .0,
+        Status::InternalServerError
+    );
+}
+
+#[rocket::async_test]
+async fn guard_forward() {
+    #[actions(rocket)]
+    mod page {
+        use rocket::{
+            Build, Request, Rocket,
+            http::Status,
+            request::{FromRequest, Outcome},
+        };
+
+        use crate as html_form_actions;
+
+        /// A guard which forwards requests without an `X-User` header.
+        pub struct User<'r>(&'r str);
+
+        #[rocket::async_trait]
+        impl<'r> FromRequest<'r> for User<'r> {
+            type Error = ();
+
+            async fn from_request(request: &'r Request<'_>) -> Outcome<Self, ()> {
+                match request.headers().get_one("X-User") {
+                    Some(user) => Outcome::Success(User(user)),
+                    None => Outcome::Forward(Status::Unauthorized),
+                }
+            }
+        }
+
+        #[action]
+        async fn my_action(#[form] a: i32, user: User<'_>) -> String {
+            std::format!("{}: a = {a}", user.0)
+        }
+
+        #[rocket::post("/", data = "<body>", rank = 100)]
+        fn forwarded(body: &str) -> String {
+            std::format!("forwarded {body}")
+        }
+
+        pub fn mount(rocket: Rocket<Build>) -> Rocket<Build> {
+            rocket
+                .mount("/basic", ActionsHandler)
+                .mount("/basic", rocket::routes![forwarded])
+        }
+    }
+
+    let client = Client::tracked(page::mount(rocket())).await.unwrap();
+
+    let response = client
+        .post("/basic?/my_action")
+        .header(ContentType::Form)
+        .header(rocket::http::Header::new("X-User", "ferris"))
+        .body("a=1")
+        .dispatch()
+        .await;
+
+    assert_eq!(response.into_string().await.unwrap(), "ferris: a = 1");
+
+    assert_eq!(
+        post(page::mount(rocket()), "/basic?/my_action", "a=1").await,
+        (Status::Ok, "forwarded a=1".into())
+    );
+}
+
+#[rocket::async_test]
+async fn fail() {
+    #[actions(rocket, reject = rejected)]
+    mod page {
+        use rocket::{Build, Rocket, http::Status};
+
+        use crate as html_form_actions;
+
+        use html_form_actions::{ActionResult, FieldError, Rejection};
+
+        #[action(fail = my_action_failed)]
+        async fn my_action(#[form] a: i32) -> ActionResult<String, my_action::Errors> {
+            if a < 0 {
+                return Err(my_action::Errors {
+                    a: Some(FieldError::Message("A must not be negative")),
+                });
+            }
+
+            Ok(std::format!("a = {a}"))
+        }
+
+        async fn my_action_failed(failure: my_action::Failure<'_>) -> (Status, String) {
+            (
+                Status::UnprocessableEntity,
+                std::format!(
+                    "{}: {}",
+                    failure.values.a.unwrap_or_default(),
+                    failure.errors.a.unwrap()
+                ),
+            )
+        }
+
+        #[action]
+        async fn other_action(#[form] b: u8) -> String {
+            std::format!("b = {b}")
+        }
+
+        async fn rejected(rejection: Rejection<'_>) -> (Status, String) {
+            (Status::BadRequest, rejection.to_string())
+        }
+
+        pub fn mount(rocket: Rocket<Build>) -> Rocket<Build> {
+            rocket.mount("/basic", ActionsHandler)
+        }
+    }
+
+    let app = || page::mount(rocket());
+
+    assert_eq!(
+        post(app(), "/basic?/my_action", "a=1").await,
+        (Status::Ok, "a = 1".into())
+    );
+    assert_eq!(
+        post(app(), "/basic?/my_action", "a=-1").await,
+        (
+            Status::UnprocessableEntity,
+            "-1: A must not be negative".into()
+        )
+    );
+    assert_eq!(
+        post(app(), "/basic?/other_action", "b=300").await,
+        (Status::BadRequest, "b: This value is invalid".into())
+    );
+}
+
+#[rocket::async_test]
+async fn form_data() {
+    #[actions(rocket)]
+    mod page {
+        use rocket::{Build, Rocket};
+
+        use crate as html_form_actions;
+
+        #[action]
+        async fn my_action(#[form] a: i32, #[form] name: &str) -> String {
+            std::format!("a = {a}, name = {name}")
+        }
+
+        pub fn mount(rocket: Rocket<Build>) -> Rocket<Build> {
+            rocket.mount("/basic", ActionsHandler)
+        }
+    }
+
+    let client = Client::tracked(page::mount(rocket())).await.unwrap();
+
+    let response = client
+        .post("/basic?/my_action")
+        .header(ContentType::new("multipart", "form-data").with_params(("boundary", "X")))
+        .body("--X\r\nContent-Disposition: form-data; name=\"a\"\r\n\r\n1\r\n--X\r\nContent-Disposition: form-data; name=\"name\"\r\n\r\nA & B\r\n--X--\r\n")
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().await.unwrap(), "a = 1, name = A & B");
+
+    let response = client
+        .post("/basic?/my_action")
+        .header(ContentType::Plain)
+        .body("a=1&name=A")
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::UnsupportedMediaType);
+}
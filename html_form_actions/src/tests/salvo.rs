@@ -0,0 +1,160 @@
+use salvo::{
+    Router, Service,
+    http::StatusCode,
+    test::{ResponseExt, TestClient},
+};
+
+use crate::actions;
+
+async fn post(router: Router, uri: &str, body: &'static str) -> (StatusCode, String) {
+    let mut response = TestClient::post(std::format!("http://localhost{uri}"))
+        .raw_form(body)
+        .send(&Service::new(router))
+        .await;
+
+    let status = response.status_code.unwrap_or(StatusCode::OK);
+
+    (status, response.take_string().await.unwrap_or_default())
+}
+
+#[tokio::test]
+async fn basic() {
+    #[actions(salvo)]
+    mod page {
+        use salvo::{
+            Depot, Extractible, Request, Router, affix_state,
+            extract::Metadata,
+            http::{Method, StatusError},
+        };
+
+        use crate as html_form_actions;
+
+        #[derive(Clone)]
+        pub struct AppState {
+            pub offset: i32,
+        }
+
+        struct RequestMethod(Method);
+
+        impl<'ex> Extractible<'ex> for RequestMethod {
+            fn metadata() -> &'static Metadata {
+                static METADATA: Metadata = Metadata::new("RequestMethod");
+                &METADATA
+            }
+
+            #[allow(refining_impl_trait)]
+            async fn extract(
+                req: &'ex mut Request,
+                _depot: &'ex mut Depot,
+            ) -> Result<Self, StatusError> {
+                Ok(Self(req.method().clone()))
+            }
+        }
+
+        #[action]
+        async fn my_action(#[form] a: i32, depot: &Depot) -> String {
+            let state = depot.get_typed::<AppState>().unwrap();
+
+            std::format!("a = {}", a + state.offset)
+        }
+
+        #[action]
+        async fn method(#[form] name: String, RequestMethod(method): RequestMethod) -> String {
+            std::format!("{method} {name}")
+        }
+
+        pub fn router() -> Router {
+            Router::new()
+                .hoop(affix_state::inject(AppState { offset: 10 }))
+                .push(Router::with_path("basic").post(ActionsHandler))
+        }
+    }
+
+    assert_eq!(
+        post(page::router(), "/basic?/my_action", "a=1").await,
+        (StatusCode::OK, "a = 11".into())
+    );
+    assert_eq!(
+        post(page::router(), "/basic?/method", "name=A+%26+B").await,
+        (StatusCode::OK, "POST A & B".into())
+    );
+    assert_eq!(
+        post(page::router(), "/basic?/my_action", "a=one").await,
+        (StatusCode::BAD_REQUEST, "Bad Form".into())
+    );
+    assert_eq!(
+        post(page::router(), "/basic?/missing", "a=1").await,
+        (StatusCode::NOT_FOUND, "Action Not Found".into())
+    );
+}
+
+#[tokio::test]
+async fn fail() {
+    #[actions(salvo(handler = NamedActionsHandler), reject = rejected)]
+    mod page {
+        use salvo::{Depot, Router, affix_state, http::StatusCode};
+
+        use crate as html_form_actions;
+
+        use html_form_actions::{ActionResult, FieldError, Rejection};
+
+        #[action(fail = my_action_failed)]
+        async fn my_action(
+            #[form] a: i32,
+            depot: &Depot,
+        ) -> ActionResult<String, my_action::Errors> {
+            if a < *depot.get_typed::<i32>().unwrap() {
+                return Err(my_action::Errors {
+                    a: Some(FieldError::Message("A is too small")),
+                });
+            }
+
+            Ok(std::format!("a = {a}"))
+        }
+
+        async fn my_action_failed(
+            failure: my_action::Failure<'_>,
+            _depot: &Depot,
+        ) -> (StatusCode, String) {
+            (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                std::format!(
+                    "{}: {}",
+                    failure.values.a.unwrap_or_default(),
+                    failure.errors.a.unwrap()
+                ),
+            )
+        }
+
+        #[action]
+        async fn other_action(#[form] b: u8) -> String {
+            std::format!("b = {b}")
+        }
+
+        async fn rejected(rejection: Rejection<'_>) -> (StatusCode, String) {
+            (StatusCode::BAD_REQUEST, rejection.to_string())
+        }
+
+        pub fn router() -> Router {
+            Router::new()
+                .hoop(affix_state::inject(0_i32))
+                .push(Router::with_path("basic").post(NamedActionsHandler))
+        }
+    }
+
+    assert_eq!(
+        post(page::router(), "/basic?/my_action", "a=1").await,
+        (StatusCode::OK, "a = 1".into())
+    );
+    assert_eq!(
+        post(page::router(), "/basic?/my_action", "a=-1").await,
+        (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "-1: A is too small".into()
+        )
+    );
+    assert_eq!(
+        post(page::router(), "/basic?/other_action", "b=300").await,
+        (StatusCode::BAD_REQUEST, "b: This value is invalid".into())
+    );
+}
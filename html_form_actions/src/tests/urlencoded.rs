@@ -0,0 +1,60 @@
+use crate::urlencoded::pairs;
+
+fn decode(body: &str) -> Vec<(String, String)> {
+    let mut body = body.as_bytes().to_vec();
+
+    pairs(&mut body)
+        .map(|pair| {
+            let (name, value) = pair.unwrap();
+            (name.into(), value.into())
+        })
+        .collect()
+}
+
+#[test]
+fn decode_pairs() {
+    assert_eq!(
+        decode("a=1&b=two+words&&c&d=%26%3D%25&e=%e2%9c%93"),
+        [
+            ("a".into(), "1".into()),
+            ("b".into(), "two words".into()),
+            ("c".into(), "".into()),
+            ("d".into(), "&=%".into()),
+            ("e".into(), "✓".into()),
+        ]
+    );
+}
+
+#[test]
+fn invalid_escapes_are_kept() {
+    assert_eq!(
+        decode("a=100%&b=%zz&c=%4"),
+        [
+            ("a".into(), "100%".into()),
+            ("b".into(), "%zz".into()),
+            ("c".into(), "%4".into()),
+        ]
+    );
+}
+
+#[test]
+fn invalid_utf8() {
+    assert!(pairs(&mut b"a=%ff".to_vec()).next().unwrap().is_err());
+}
+
+#[test]
+fn encode() {
+    use crate::urlencoded::Encoded;
+
+    assert_eq!(Encoded("a-b_c.d*9").to_string(), "a-b_c.d*9");
+    assert_eq!(Encoded("two words").to_string(), "two+words");
+    assert_eq!(Encoded("&=%+✓").to_string(), "%26%3D%25%2B%E2%9C%93");
+    assert_eq!(Encoded("items[3].qty").to_string(), "items%5B3%5D.qty");
+
+    let mut body = Encoded("&=% ✓").to_string().into_bytes();
+
+    assert_eq!(
+        crate::urlencoded::decode_in_place(&mut body),
+        Ok(&mut *String::from("&=% ✓"))
+    );
+}
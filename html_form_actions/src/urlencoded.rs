@@ -0,0 +1,165 @@
+//! Decoding and encoding of `application/x-www-form-urlencoded` form submissions.
+//!
+//! Decoding is performed in place, so no allocation is required, and decoded names and values borrow from the body buffer.
+//! Encoding is performed while formatting, so also requires no allocation.
+
+use core::{fmt, ops::ControlFlow};
+
+use crate::{FieldName, FormValue, NamePrefix};
+
+/// An iterator over the decoded name-value pairs of a form submission.
+///
+/// Created by [`pairs`].
+pub struct Pairs<'a> {
+    remaining: &'a mut [u8],
+}
+
+/// Decode the name-value pairs of a form submission in place.
+///
+/// Empty entries are skipped, and entries without a `=` are treated as having an empty value.
+pub fn pairs(body: &mut [u8]) -> Pairs<'_> {
+    Pairs { remaining: body }
+}
+
+fn split_once_mut(bytes: &mut [u8], separator: u8) -> (&mut [u8], &mut [u8]) {
+    match bytes.iter().position(|&byte| byte == separator) {
+        Some(index) => {
+            let (head, tail) = bytes.split_at_mut(index);
+            (head, &mut tail[1..])
+        }
+        None => (bytes, Default::default()),
+    }
+}
+
+impl<'a> Iterator for Pairs<'a> {
+    type Item = Result<(&'a str, &'a str), core::str::Utf8Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.remaining.is_empty() {
+                return None;
+            }
+
+            let (entry, remaining) = split_once_mut(core::mem::take(&mut self.remaining), b'&');
+
+            self.remaining = remaining;
+
+            if entry.is_empty() {
+                continue;
+            }
+
+            let (name, value) = split_once_mut(entry, b'=');
+
+            return Some(
+                decode_in_place(name).and_then(|name| Ok((&*name, &*decode_in_place(value)?))),
+            );
+        }
+    }
+}
+
+fn hex_digit(byte: u8) -> Option<u8> {
+    match byte {
+        b'0'..=b'9' => Some(byte - b'0'),
+        b'a'..=b'f' => Some(byte - b'a' + 10),
+        b'A'..=b'F' => Some(byte - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Decode a single urlencoded name or value in place, returning the decoded text.
+///
+/// `+` is decoded as a space, and `%` followed by two hex digits is decoded as the corresponding byte.
+/// Any other `%` is left as is.
+pub fn decode_in_place(bytes: &mut [u8]) -> Result<&mut str, core::str::Utf8Error> {
+    let mut read_index = 0;
+    let mut write_index = 0;
+
+    while let Some(&byte) = bytes.get(read_index) {
+        let (decoded, encoded_length) = match byte {
+            b'+' => (b' ', 1),
+            b'%' => match bytes
+                .get(read_index + 1..read_index + 3)
+                .and_then(|digits| Some(hex_digit(digits[0])? << 4 | hex_digit(digits[1])?))
+            {
+                Some(decoded) => (decoded, 3),
+                None => (b'%', 1),
+            },
+            byte => (byte, 1),
+        };
+
+        bytes[write_index] = decoded;
+
+        read_index += encoded_length;
+        write_index += 1;
+    }
+
+    core::str::from_utf8_mut(&mut bytes[..write_index])
+}
+
+/// A name or value which is encoded while formatting.
+///
+/// Spaces are encoded as `+`, and all bytes other than ASCII alphanumerics and `*-._` are percent-encoded.
+pub struct Encoded<T>(pub T);
+
+/// Encodes text written to the inner writer.
+struct EncodingWriter<'a, W>(&'a mut W);
+
+impl<W: fmt::Write> fmt::Write for EncodingWriter<'_, W> {
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        for byte in text.bytes() {
+            match byte {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'*' | b'-' | b'.' | b'_' => {
+                    self.0.write_char(byte.into())?
+                }
+                b' ' => self.0.write_char('+')?,
+                byte => write!(self.0, "%{byte:02X}")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Encoded<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Write::write_fmt(&mut EncodingWriter(f), format_args!("{}", self.0))
+    }
+}
+
+/// Calls `visit` with the name and value of each submitted field of `values`, nested within the given prefix,
+/// stopping if `visit` returns [`ControlFlow::Break`].
+pub type VisitPairs<V> = fn(
+    NamePrefix<'_>,
+    &V,
+    &mut dyn FnMut(FieldName<'_>, FormValue<'_>) -> ControlFlow<()>,
+) -> ControlFlow<()>;
+
+/// The submitted values of a form, encoded as `application/x-www-form-urlencoded` while formatting.
+///
+/// Created by the `to_urlencoded` method of a generated `Values`, or by [`Submission::body`](crate::Submission::body).
+pub struct UrlEncoded<'a, V> {
+    /// The submitted values.
+    pub values: &'a V,
+    /// Visit the name and value of each submitted field.
+    pub visit_pairs: VisitPairs<V>,
+}
+
+impl<V> fmt::Display for UrlEncoded<'_, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut result = Ok(());
+        let mut separator = "";
+
+        let _ = (self.visit_pairs)(NamePrefix::Root, self.values, &mut |name, value| {
+            result = write!(f, "{separator}{}={}", Encoded(name), Encoded(value));
+            separator = "&";
+
+            if result.is_ok() {
+                ControlFlow::Continue(())
+            } else {
+                ControlFlow::Break(())
+            }
+        });
+
+        result
+    }
+}
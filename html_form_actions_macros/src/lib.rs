@@ -1,4 +1,5 @@
 use quote::ToTokens;
+use syn::ext::IdentExt;
 use syn::spanned::Spanned;
 
 fn maybe_extract_attributes<T: deluxe::HasAttributes, R: deluxe::ExtractAttributes<T>>(
@@ -11,239 +12,2225 @@ fn maybe_extract_attributes<T: deluxe::HasAttributes, R: deluxe::ExtractAttribut
         .transpose()
 }
 
+/// A `min`, `max` or `step` constraint, as used in the HTML attribute.
+struct ConstraintValue(String);
+
+impl ConstraintValue {
+    fn from_expr(expr: &syn::Expr) -> syn::Result<Self> {
+        match expr {
+            syn::Expr::Lit(syn::ExprLit { lit, .. }) => match lit {
+                syn::Lit::Int(lit) => Ok(Self(lit.base10_digits().into())),
+                syn::Lit::Float(lit) => Ok(Self(lit.base10_digits().into())),
+                syn::Lit::Str(lit) => Ok(Self(lit.value())),
+                _ => Err(syn::Error::new(lit.span(), "expected a number or string")),
+            },
+            syn::Expr::Unary(syn::ExprUnary {
+                op: syn::UnOp::Neg(_),
+                expr,
+                ..
+            }) => Ok(Self(format!("-{}", Self::from_expr(expr)?.0))),
+            _ => Err(syn::Error::new(expr.span(), "expected a literal")),
+        }
+    }
+}
+
+impl deluxe::ParseMetaItem for ConstraintValue {
+    fn parse_meta_item(
+        input: syn::parse::ParseStream,
+        _mode: deluxe::ParseMode,
+    ) -> deluxe::Result<Self> {
+        Self::from_expr(&input.parse()?)
+    }
+}
+
+#[derive(Default)]
+struct FieldConstraints {
+    required: bool,
+    min: Option<ConstraintValue>,
+    max: Option<ConstraintValue>,
+    step: Option<ConstraintValue>,
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+    pattern: Option<syn::LitStr>,
+}
+
+impl FieldConstraints {
+    /// The `Constraints` to check the submitted value against, if there are any.
+    fn check(&self) -> Option<proc_macro2::TokenStream> {
+        let Self {
+            required,
+            min,
+            max,
+            step,
+            min_length,
+            max_length,
+            pattern,
+        } = self;
+
+        let required = required.then(|| quote::quote! { required: true, });
+        let min = min
+            .as_ref()
+            .map(|ConstraintValue(min)| quote::quote! { min: Some(#min), });
+        let max = max
+            .as_ref()
+            .map(|ConstraintValue(max)| quote::quote! { max: Some(#max), });
+        let step = step
+            .as_ref()
+            .map(|ConstraintValue(step)| quote::quote! { step: Some(#step), });
+        let min_length =
+            min_length.map(|min_length| quote::quote! { min_length: Some(#min_length), });
+        let max_length =
+            max_length.map(|max_length| quote::quote! { max_length: Some(#max_length), });
+        let pattern = pattern
+            .as_ref()
+            .map(|pattern| {
+                quote::quote! {
+                    pattern: Some({
+                        static PATTERN: html_form_actions::Pattern = html_form_actions::Pattern::new(#pattern);
+
+                        &PATTERN
+                    }),
+                }
+            });
+
+        let fields = [required, min, max, step, min_length, max_length, pattern]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+
+        (!fields.is_empty()).then(|| {
+            quote::quote! {
+                html_form_actions::Constraints {
+                    #(#fields)*
+                    ..html_form_actions::Constraints::NONE
+                }
+            }
+        })
+    }
+}
+
+/// A field of an action's `Form`, with its type and value.
+struct FormMetadata {
+    ident: syn::Ident,
+    ty: proc_macro2::TokenStream,
+    value: proc_macro2::TokenStream,
+}
+
 struct ActionFormInput {
     ident: syn::Ident,
     rename: Option<syn::Expr>,
     form_name: syn::Ident,
     ty: syn::Type,
+    input_type: Option<String>,
+    label: Option<String>,
+    placeholder: Option<String>,
+    help: Option<String>,
+    constraints: FieldConstraints,
 }
 
-struct Action {
+impl ActionFormInput {
+    /// The fields of the action's `Form` which describe this input, whose ids start with `id_prefix`, if any.
+    ///
+    /// The ids of the fields of structs deriving `HtmlForm` have no prefix, as it depends on the action the struct is used in.
+    fn form_metadata(&self, id_prefix: Option<&str>) -> Vec<FormMetadata> {
+        let Self {
+            ident,
+            rename,
+            form_name,
+            ty,
+            input_type,
+            label,
+            placeholder,
+            help,
+            constraints:
+                FieldConstraints {
+                    required,
+                    min,
+                    max,
+                    step,
+                    min_length,
+                    max_length,
+                    pattern,
+                },
+        } = self;
+
+        let metadata = |suffix: &str,
+                        ty: proc_macro2::TokenStream,
+                        value: proc_macro2::TokenStream| FormMetadata {
+            ident: syn::Ident::new(&format!("{ident}_{suffix}"), ident.span()),
+            ty,
+            value,
+        };
+
+        let name = rename.as_ref().map_or_else(
+            || ident.to_string().to_token_stream(),
+            quote::ToTokens::to_token_stream,
+        );
+
+        let label = label
+            .clone()
+            .unwrap_or_else(|| sentence_case(&ident.unraw().to_string()));
+
+        let optional_str = |value: &Option<String>| {
+            value.as_ref().map_or_else(
+                || quote::quote! { None },
+                |value| quote::quote! { Some(#value) },
+            )
+        };
+
+        let id = match id_prefix {
+            Some(id_prefix) => format!("{id_prefix}-{ident}"),
+            None => ident.to_string(),
+        };
+        let error_id = format!("{id}-error");
+
+        let str_type = quote::quote! { &'static str };
+        let usize_type = quote::quote! { usize };
+
+        let input_type = input_type.as_ref().map_or_else(
+            || quote::quote! { <#ty as html_form_actions::FormField<'static>>::INPUT_TYPE },
+            quote::ToTokens::to_token_stream,
+        );
+
+        // Unlike other constraints, the step may be derived from the type, so is always present
+        let step = step.as_ref().map_or_else(
+            || quote::quote! { <#ty as html_form_actions::FormField<'static>>::STEP },
+            |ConstraintValue(step)| quote::quote! { Some(#step) },
+        );
+
+        // Likewise, the maximum length may be derived from the capacity of the type, such as `heapless::String<N>`
+        let max_length = max_length.map_or_else(
+            || quote::quote! { <#ty as html_form_actions::FormField<'static>>::MAX_LENGTH },
+            |max_length| quote::quote! { Some(#max_length) },
+        );
+
+        [
+            Some(FormMetadata {
+                ident: form_name.clone(),
+                ty: str_type.clone(),
+                value: name,
+            }),
+            Some(metadata("id", str_type.clone(), id.to_token_stream())),
+            Some(metadata(
+                "error_id",
+                str_type.clone(),
+                error_id.to_token_stream(),
+            )),
+            Some(metadata("label", str_type.clone(), label.to_token_stream())),
+            Some(metadata(
+                "placeholder",
+                quote::quote! { Option<&'static str> },
+                optional_str(placeholder),
+            )),
+            Some(metadata(
+                "help",
+                quote::quote! { Option<&'static str> },
+                optional_str(help),
+            )),
+            Some(metadata("input_type", str_type.clone(), input_type)),
+            Some(metadata(
+                "multiple",
+                quote::quote! { bool },
+                quote::quote! { <#ty as html_form_actions::FormField<'static>>::MULTIPLE },
+            )),
+            Some(metadata(
+                "options",
+                quote::quote! { &'static [(&'static str, &'static str)] },
+                quote::quote! { <#ty as html_form_actions::FormField<'static>>::OPTIONS },
+            )),
+            required.then(|| metadata("required", quote::quote! { bool }, quote::quote! { true })),
+            min.as_ref().map(|ConstraintValue(min)| {
+                metadata("min", str_type.clone(), min.to_token_stream())
+            }),
+            max.as_ref().map(|ConstraintValue(max)| {
+                metadata("max", str_type.clone(), max.to_token_stream())
+            }),
+            Some(metadata(
+                "step",
+                quote::quote! { Option<&'static str> },
+                step,
+            )),
+            min_length
+                .map(|min_length| metadata("min_length", usize_type, min_length.to_token_stream())),
+            Some(metadata(
+                "max_length",
+                quote::quote! { Option<usize> },
+                max_length,
+            )),
+            pattern
+                .as_ref()
+                .map(|pattern| metadata("pattern", str_type.clone(), pattern.to_token_stream())),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+}
+
+#[derive(Default, deluxe::ExtractAttributes)]
+#[deluxe(attributes(form))]
+struct FormAttrs {
+    #[deluxe(default)]
+    flatten: bool,
+    #[deluxe(default)]
+    nested: bool,
+    #[deluxe(default)]
+    rename: Option<syn::Expr>,
+    #[deluxe(default)]
+    input_type: Option<String>,
+    #[deluxe(default)]
+    label: Option<String>,
+    #[deluxe(default)]
+    placeholder: Option<String>,
+    #[deluxe(default)]
+    help: Option<String>,
+    #[deluxe(default)]
+    required: bool,
+    #[deluxe(default)]
+    min: Option<ConstraintValue>,
+    #[deluxe(default)]
+    max: Option<ConstraintValue>,
+    #[deluxe(default)]
+    step: Option<ConstraintValue>,
+    #[deluxe(default)]
+    min_length: Option<usize>,
+    #[deluxe(default)]
+    max_length: Option<usize>,
+    #[deluxe(default)]
+    pattern: Option<syn::LitStr>,
+}
+
+/// A `#[form(flatten)]` input, whose type implements `HtmlForm`.
+struct FlattenedFormInput {
     ident: syn::Ident,
-    form: Vec<ActionFormInput>,
-    other_arguments: Vec<syn::Ident>,
+    ty: syn::Type,
 }
 
-impl Action {
-    fn extract(items: &mut [syn::Item]) -> syn::Result<Vec<Self>> {
-        #[derive(deluxe::ExtractAttributes)]
-        #[deluxe(attributes(action))]
-        struct ActionAttribute {}
+/// A `#[form(nested)]` input, whose type implements `NestedForm`.
+struct NestedFormInput {
+    ident: syn::Ident,
+    rename: Option<syn::Expr>,
+    form_name: syn::Ident,
+    ty: syn::Type,
+}
 
-        let mut actions = Vec::new();
+/// A `#[form]` parameter of an action, or a field of a `#[derive(HtmlForm)]` struct.
+#[allow(clippy::large_enum_variant)]
+enum FormInput {
+    Field(ActionFormInput),
+    Flatten(FlattenedFormInput),
+    Nested(NestedFormInput),
+}
 
-        for item in items {
-            let syn::Item::Fn(f) = item else {
-                continue;
-            };
+impl FormInput {
+    fn new(
+        ident: syn::Ident,
+        ty: syn::Type,
+        FormAttrs {
+            flatten,
+            nested,
+            rename,
+            input_type,
+            label,
+            placeholder,
+            help,
+            required,
+            min,
+            max,
+            step,
+            min_length,
+            max_length,
+            pattern,
+        }: FormAttrs,
+    ) -> syn::Result<Self> {
+        if let Some(pattern) = &pattern {
+            // Patterns are checked as in `Constraints::check`, which matches the entire value
+            regex_lite::Regex::new(&format!("^(?:{})$", pattern.value())).map_err(|error| {
+                syn::Error::new(pattern.span(), format!("invalid pattern: {error}"))
+            })?;
+        }
 
-            let Some(ActionAttribute {}) = maybe_extract_attributes(f)? else {
-                continue;
-            };
+        let constraints = FieldConstraints {
+            required,
+            min,
+            max,
+            step,
+            min_length,
+            max_length,
+            pattern,
+        };
+
+        let form_name = syn::Ident::new(&format!("{ident}_name"), ident.span());
+
+        let has_field_options = input_type.is_some()
+            || label.is_some()
+            || placeholder.is_some()
+            || help.is_some()
+            || constraints.check().is_some();
+
+        if flatten {
+            if nested || rename.is_some() || has_field_options {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    "flattened form inputs cannot have other options",
+                ));
+            }
+
+            return Ok(Self::Flatten(FlattenedFormInput { ident, ty }));
+        }
+
+        if nested {
+            if has_field_options {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    "nested form inputs cannot have options other than `rename`",
+                ));
+            }
+
+            return Ok(Self::Nested(NestedFormInput {
+                ident,
+                rename,
+                form_name,
+                ty,
+            }));
+        }
+
+        Ok(Self::Field(ActionFormInput {
+            ident,
+            rename,
+            form_name,
+            ty,
+            input_type,
+            label,
+            placeholder,
+            help,
+            constraints,
+        }))
+    }
+
+    fn ident(&self) -> &syn::Ident {
+        match self {
+            Self::Field(ActionFormInput { ident, .. })
+            | Self::Flatten(FlattenedFormInput { ident, .. })
+            | Self::Nested(NestedFormInput { ident, .. }) => ident,
+        }
+    }
+
+    fn ty(&self) -> &syn::Type {
+        match self {
+            Self::Field(ActionFormInput { ty, .. })
+            | Self::Flatten(FlattenedFormInput { ty, .. })
+            | Self::Nested(NestedFormInput { ty, .. }) => ty,
+        }
+    }
+
+    /// The type of this input with its elided lifetimes replaced by `'r`, such as `&'r str`, to be used as the type of a field.
+    fn field_type(&self) -> syn::Type {
+        let mut ty = self.ty().clone();
+
+        borrow_for(
+            &mut ty,
+            &syn::Lifetime::new("'r", proc_macro2::Span::call_site()),
+        );
+
+        ty
+    }
+
+    /// Whether this input borrows from the request body, such as `&str` or `Cow<str>`.
+    fn borrows(&self) -> bool {
+        borrow_for(
+            &mut self.ty().clone(),
+            &syn::Lifetime::new("'r", proc_macro2::Span::call_site()),
+        )
+    }
+
+    /// The fields of the `Form` which describe this input, whose ids start with `id_prefix`, if any.
+    fn form_metadata(&self, id_prefix: Option<&str>) -> Vec<FormMetadata> {
+        match self {
+            Self::Field(field) => field.form_metadata(id_prefix),
+            Self::Flatten(FlattenedFormInput { ident, ty }) => vec![FormMetadata {
+                ident: ident.clone(),
+                ty: quote::quote! { <#ty as html_form_actions::HtmlForm<'static>>::Form },
+                value: quote::quote! { <#ty as html_form_actions::HtmlForm<'static>>::FORM },
+            }],
+            Self::Nested(NestedFormInput {
+                ident,
+                rename,
+                form_name,
+                ..
+            }) => vec![FormMetadata {
+                ident: form_name.clone(),
+                ty: quote::quote! { &'static str },
+                value: rename.as_ref().map_or_else(
+                    || ident.to_string().to_token_stream(),
+                    quote::ToTokens::to_token_stream,
+                ),
+            }],
+        }
+    }
+
+    /// The method of `Form` which builds the names and ids of the fields of this input, if it is flattened or nested,
+    /// within a form whose fields have the prefix `root`.
+    fn form_names_method(
+        &self,
+        vis: &proc_macro2::TokenStream,
+        root: &proc_macro2::TokenStream,
+    ) -> Option<proc_macro2::TokenStream> {
+        match self {
+            Self::Field(_) => None,
+            Self::Flatten(FlattenedFormInput { ident, ty }) => Some(quote::quote! {
+                #vis fn #ident(&self) -> <#ty as html_form_actions::HtmlForm<'static>>::Names<'static> {
+                    <#ty as html_form_actions::HtmlForm>::names(#root)
+                }
+            }),
+            Self::Nested(NestedFormInput {
+                ident,
+                form_name,
+                ty,
+                ..
+            }) => Some(quote::quote! {
+                #vis fn #ident(&self) -> <#ty as html_form_actions::NestedForm<'static>>::Names<'static> {
+                    <#ty as html_form_actions::NestedForm>::names(&#root, self.#form_name)
+                }
+            }),
+        }
+    }
+
+    /// The methods of `Names` which build the name and ids of this input, or the names and ids of its fields.
+    fn names_method(&self) -> proc_macro2::TokenStream {
+        match self {
+            Self::Field(ActionFormInput {
+                ident, form_name, ..
+            }) => {
+                let [id, error_id] = ["id", "error_id"]
+                    .map(|suffix| syn::Ident::new(&format!("{ident}_{suffix}"), ident.span()));
+
+                quote::quote! {
+                    pub fn #form_name(&self) -> html_form_actions::FieldName<'p> {
+                        html_form_actions::FieldName {
+                            prefix: self.prefix,
+                            name: FORM.#form_name,
+                        }
+                    }
+
+                    pub fn #id(&self) -> html_form_actions::FieldId<'p> {
+                        html_form_actions::FieldId {
+                            prefix: self.prefix,
+                            id: FORM.#id,
+                        }
+                    }
+
+                    pub fn #error_id(&self) -> html_form_actions::FieldId<'p> {
+                        html_form_actions::FieldId {
+                            prefix: self.prefix,
+                            id: FORM.#error_id,
+                        }
+                    }
+                }
+            }
+            Self::Flatten(FlattenedFormInput { ident, ty }) => quote::quote! {
+                pub fn #ident(&self) -> <#ty as html_form_actions::HtmlForm<'static>>::Names<'p> {
+                    <#ty as html_form_actions::HtmlForm>::names(self.prefix)
+                }
+            },
+            Self::Nested(NestedFormInput {
+                ident,
+                form_name,
+                ty,
+                ..
+            }) => quote::quote! {
+                pub fn #ident(&self) -> <#ty as html_form_actions::NestedForm<'static>>::Names<'_> {
+                    <#ty as html_form_actions::NestedForm>::names(&self.prefix, FORM.#form_name)
+                }
+            },
+        }
+    }
+
+    /// The type of the field of `Values` containing the raw submitted values of this input.
+    fn values_type(&self) -> proc_macro2::TokenStream {
+        let ty = &self.field_type();
+
+        match self {
+            Self::Field(_) => {
+                quote::quote! { <#ty as html_form_actions::FormField<'r>>::Values }
+            }
+            Self::Flatten(_) => {
+                quote::quote! { <#ty as html_form_actions::HtmlForm<'r>>::Values }
+            }
+            Self::Nested(_) => {
+                quote::quote! { <#ty as html_form_actions::NestedForm<'r>>::Values }
+            }
+        }
+    }
+
+    /// The type of the field of `Errors` containing the reason this input was rejected.
+    fn errors_type(&self, lifetime: &syn::Lifetime) -> proc_macro2::TokenStream {
+        match self {
+            Self::Field(_) => quote::quote! { Option<html_form_actions::FieldError> },
+            Self::Flatten(FlattenedFormInput { ty, .. }) => {
+                quote::quote! { <#ty as html_form_actions::HtmlForm<#lifetime>>::Errors }
+            }
+            Self::Nested(NestedFormInput { ty, .. }) => {
+                quote::quote! { <#ty as html_form_actions::NestedForm<#lifetime>>::Errors }
+            }
+        }
+    }
+
+    /// The type of the field of `Prefill` containing the values used to prefill the HTML inputs of this input.
+    fn prefill_type(&self) -> proc_macro2::TokenStream {
+        match self {
+            Self::Field(_) => quote::quote! { Option<html_form_actions::FormValue<'a>> },
+            Self::Flatten(FlattenedFormInput { ty, .. }) => {
+                quote::quote! { <#ty as html_form_actions::HtmlForm<'static>>::Prefill<'a> }
+            }
+            Self::Nested(NestedFormInput { ty, .. }) => {
+                quote::quote! { <#ty as html_form_actions::NestedForm<'static>>::Prefill<'a> }
+            }
+        }
+    }
+
+    /// The values used to prefill the HTML inputs of this input with the submitted `values`.
+    fn prefill(&self) -> proc_macro2::TokenStream {
+        match self {
+            Self::Field(ActionFormInput { ident, .. }) => quote::quote! {
+                html_form_actions::FieldValues::as_slice(&values.#ident)
+                    .first()
+                    .copied()
+                    .map(html_form_actions::FormValue::Raw)
+            },
+            Self::Flatten(FlattenedFormInput { ident, ty }) => quote::quote! {
+                <#ty as html_form_actions::HtmlForm>::prefill(&values.#ident)
+            },
+            Self::Nested(NestedFormInput { ident, ty, .. }) => quote::quote! {
+                <#ty as html_form_actions::NestedForm>::prefill(&values.#ident)
+            },
+        }
+    }
+
+    /// Record `value` into `values`, if `name` is the name of this input.
+    fn collect_value(&self, form: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        match self {
+            Self::Field(ActionFormInput {
+                ident, form_name, ..
+            }) => quote::quote! {
+                if name == #form.#form_name
+                    && let Err(error) = html_form_actions::FieldValues::push(&mut values.#ident, value)
+                {
+                    errors.#ident = Some(error);
+                }
+            },
+            Self::Flatten(FlattenedFormInput { ident, ty }) => quote::quote! {
+                <#ty as html_form_actions::HtmlForm>::push_value(&mut values.#ident, &mut errors.#ident, name, value);
+            },
+            Self::Nested(NestedFormInput {
+                ident,
+                form_name,
+                ty,
+                ..
+            }) => quote::quote! {
+                if let Some(name) = name.strip_prefix(#form.#form_name) {
+                    <#ty as html_form_actions::NestedForm>::push_value(&mut values.#ident, &mut errors.#ident, name, value);
+                }
+            },
+        }
+    }
+
+    /// Parse this input from `values` into an `Option` with the same name as the input, recording any error into `errors`.
+    fn parse(&self) -> proc_macro2::TokenStream {
+        match self {
+            Self::Field(ActionFormInput {
+                ident,
+                ty,
+                constraints,
+                ..
+            }) => {
+                let check_constraints = constraints.check().map(|constraints| {
+                    quote::quote! {
+                        .and_then(|value| {
+                            #constraints
+                                .check_all(html_form_actions::FieldValues::as_slice(&values.#ident))
+                                .map(|()| value)
+                        })
+                    }
+                });
+
+                quote::quote! {
+                    // Fields with too many values have already been rejected
+                    let #ident = if errors.#ident.is_none() {
+                        <#ty as html_form_actions::FormField>::from_form_field(&values.#ident)
+                            #check_constraints
+                            .map_err(|error| errors.#ident = Some(error))
+                            .ok()
+                    } else {
+                        None
+                    };
+                }
+            }
+            Self::Flatten(FlattenedFormInput { ident, ty }) => quote::quote! {
+                let #ident = <#ty as html_form_actions::HtmlForm>::from_form(&values.#ident, &mut errors.#ident);
+            },
+            Self::Nested(NestedFormInput { ident, ty, .. }) => quote::quote! {
+                let #ident = <#ty as html_form_actions::NestedForm>::from_form(&values.#ident, &mut errors.#ident);
+            },
+        }
+    }
+
+    /// The reason this input was rejected, if it was.
+    fn first_error(&self) -> proc_macro2::TokenStream {
+        match self {
+            Self::Field(ActionFormInput { ident, .. }) => quote::quote! { errors.#ident },
+            Self::Flatten(FlattenedFormInput { ident, ty }) => quote::quote! {
+                <#ty as html_form_actions::HtmlForm>::first_error(&errors.#ident)
+            },
+            Self::Nested(NestedFormInput { ident, ty, .. }) => quote::quote! {
+                <#ty as html_form_actions::NestedForm>::first_error(&errors.#ident)
+            },
+        }
+    }
+
+    /// Statements which call `visit` with the name and value of each submitted field of this input.
+    fn visit_pairs(&self, form: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        match self {
+            Self::Field(ActionFormInput {
+                ident, form_name, ..
+            }) => quote::quote! {
+                for value in html_form_actions::FieldValues::as_slice(&values.#ident) {
+                    visit(
+                        html_form_actions::FieldName { prefix, name: #form.#form_name },
+                        html_form_actions::FormValue::Raw(value),
+                    )?;
+                }
+            },
+            Self::Flatten(FlattenedFormInput { ident, ty }) => quote::quote! {
+                <#ty as html_form_actions::HtmlForm>::visit_pairs(prefix, &values.#ident, visit)?;
+            },
+            Self::Nested(NestedFormInput {
+                ident,
+                form_name,
+                ty,
+                ..
+            }) => quote::quote! {
+                <#ty as html_form_actions::NestedForm>::visit_pairs(&prefix, #form.#form_name, &values.#ident, visit)?;
+            },
+        }
+    }
+
+    /// The type parameter of this input in `Typed`.
+    fn typed_param(&self) -> syn::Ident {
+        pascal_case(self.ident())
+    }
+
+    /// The bound on the type parameter of this input in `Typed`.
+    fn typed_bound(&self) -> proc_macro2::TokenStream {
+        let param = self.typed_param();
+
+        match self {
+            Self::Field(_) => quote::quote! { #param: html_form_actions::ToFormField },
+            Self::Flatten(_) => quote::quote! { #param: html_form_actions::ToHtmlForm },
+            Self::Nested(_) => quote::quote! { #param: html_form_actions::ToNestedForm },
+        }
+    }
+
+    /// Statements which call `visit` with the name and value of each field of this input in `Typed`.
+    fn visit_typed_pairs(&self, form: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        match self {
+            Self::Field(ActionFormInput {
+                ident, form_name, ..
+            }) => quote::quote! {
+                html_form_actions::ToFormField::visit_form_values(&self.#ident, &mut |value| {
+                    visit(
+                        html_form_actions::FieldName { prefix, name: #form.#form_name },
+                        html_form_actions::FormValue::Typed(value),
+                    )
+                })?;
+            },
+            Self::Flatten(FlattenedFormInput { ident, .. }) => quote::quote! {
+                html_form_actions::ToHtmlForm::visit_pairs(&self.#ident, prefix, visit)?;
+            },
+            Self::Nested(NestedFormInput {
+                ident, form_name, ..
+            }) => quote::quote! {
+                html_form_actions::ToNestedForm::visit_pairs(&self.#ident, &prefix, #form.#form_name, visit)?;
+            },
+        }
+    }
+
+    /// Statements which render this input as HTML, prefilled with `values` and showing `errors`.
+    fn render(&self, form: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        match self {
+            Self::Field(ActionFormInput {
+                ident,
+                form_name,
+                constraints:
+                    FieldConstraints {
+                        required,
+                        min,
+                        max,
+                        min_length,
+                        pattern,
+                        ..
+                    },
+                ..
+            }) => {
+                let form_field =
+                    |suffix: &str| syn::Ident::new(&format!("{ident}_{suffix}"), ident.span());
+
+                // The ids are prefixed when rendered, so are the ids within the form, as for structs deriving `HtmlForm`
+                let id = ident.to_string();
+                let error_id = format!("{id}-error");
+
+                let [
+                    label,
+                    placeholder,
+                    help,
+                    input_type,
+                    multiple,
+                    options,
+                    step,
+                    max_length,
+                ] = [
+                    "label",
+                    "placeholder",
+                    "help",
+                    "input_type",
+                    "multiple",
+                    "options",
+                    "step",
+                    "max_length",
+                ]
+                .map(form_field);
+
+                let optional = |value: Option<proc_macro2::TokenStream>| {
+                    value.map_or_else(
+                        || quote::quote! { None },
+                        |value| quote::quote! { Some(#value) },
+                    )
+                };
+
+                let min = optional(
+                    min.as_ref()
+                        .map(|ConstraintValue(min)| min.to_token_stream()),
+                );
+                let max = optional(
+                    max.as_ref()
+                        .map(|ConstraintValue(max)| max.to_token_stream()),
+                );
+                let min_length =
+                    optional(min_length.map(|min_length| min_length.to_token_stream()));
+                let pattern = optional(pattern.as_ref().map(|pattern| pattern.to_token_stream()));
+
+                quote::quote! {
+                    core::fmt::Display::fmt(
+                        &html_form_actions::FieldDescription {
+                            name: #form.#form_name,
+                            id: #id,
+                            error_id: #error_id,
+                            label: #form.#label,
+                            placeholder: #form.#placeholder,
+                            help: #form.#help,
+                            input_type: #form.#input_type,
+                            multiple: #form.#multiple,
+                            options: #form.#options,
+                            required: #required,
+                            min: #min,
+                            max: #max,
+                            step: #form.#step,
+                            min_length: #min_length,
+                            max_length: #form.#max_length,
+                            pattern: #pattern,
+                        }
+                        .render(prefix, html_form_actions::FieldValues::as_slice(&values.#ident), errors.#ident),
+                        f,
+                    )?;
+                }
+            }
+            Self::Flatten(FlattenedFormInput { ident, ty }) => quote::quote! {
+                <#ty as html_form_actions::HtmlForm>::render_fields(f, prefix, &values.#ident, &errors.#ident)?;
+            },
+            Self::Nested(NestedFormInput {
+                ident,
+                form_name,
+                ty,
+                ..
+            }) => quote::quote! {
+                <#ty as html_form_actions::NestedForm>::render_fields(f, &prefix, #form.#form_name, &values.#ident, &errors.#ident)?;
+            },
+        }
+    }
+
+    /// The number of fields in a [`Rejection`] of this input.
+    fn field_count(&self) -> proc_macro2::TokenStream {
+        match self {
+            Self::Field(_) | Self::Nested(_) => quote::quote! { 1 },
+            Self::Flatten(FlattenedFormInput { ty, .. }) => {
+                quote::quote! { <#ty as html_form_actions::HtmlForm<'static>>::FIELD_COUNT }
+            }
+        }
+    }
+
+    /// An iterator over the fields of a [`Rejection`] of this input.
+    ///
+    /// A nested input is a single field, with the first error of its nested fields.
+    fn rejected_fields(&self, form: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        match self {
+            Self::Field(ActionFormInput {
+                ident, form_name, ..
+            }) => quote::quote! {
+                core::iter::once(html_form_actions::RejectedField {
+                    name: #form.#form_name,
+                    value: html_form_actions::FieldValues::as_slice(&values.#ident).first().copied(),
+                    error: errors.#ident,
+                })
+            },
+            Self::Flatten(FlattenedFormInput { ident, ty }) => quote::quote! {
+                <#ty as html_form_actions::HtmlForm>::rejected_fields(&values.#ident, &errors.#ident)
+            },
+            Self::Nested(NestedFormInput { form_name, .. }) => {
+                let first_error = self.first_error();
+
+                quote::quote! {
+                    core::iter::once(html_form_actions::RejectedField {
+                        name: #form.#form_name,
+                        value: None,
+                        error: #first_error,
+                    })
+                }
+            }
+        }
+    }
+}
+
+/// Replace the elided lifetimes of `ty` with `lifetime`, such as `&str` with `&'r str`, and `Cow<str>` with `Cow<'r, str>`,
+/// returning whether `ty` contains any lifetimes.
+fn borrow_for(ty: &mut syn::Type, lifetime: &syn::Lifetime) -> bool {
+    let is_elided =
+        |elided: &Option<syn::Lifetime>| elided.as_ref().is_none_or(|elided| elided.ident == "_");
+
+    match ty {
+        syn::Type::Reference(reference) => {
+            if is_elided(&reference.lifetime) {
+                reference.lifetime = Some(lifetime.clone());
+            }
+
+            borrow_for(&mut reference.elem, lifetime);
+
+            true
+        }
+        syn::Type::Path(syn::TypePath { qself, path }) => {
+            let mut borrows = qself
+                .as_mut()
+                .is_some_and(|qself| borrow_for(&mut qself.ty, lifetime));
+
+            for segment in &mut path.segments {
+                let syn::PathArguments::AngleBracketed(arguments) = &mut segment.arguments else {
+                    continue;
+                };
+
+                let mut has_lifetime = false;
+
+                for argument in &mut arguments.args {
+                    match argument {
+                        syn::GenericArgument::Lifetime(argument) => {
+                            has_lifetime = true;
+
+                            if argument.ident == "_" {
+                                *argument = lifetime.clone();
+                            }
+                        }
+                        syn::GenericArgument::Type(argument) => {
+                            borrows |= borrow_for(argument, lifetime);
+                        }
+                        _ => {}
+                    }
+                }
+
+                // The lifetime of `Cow<str>` is hidden
+                if segment.ident == "Cow" && !has_lifetime {
+                    arguments
+                        .args
+                        .insert(0, syn::GenericArgument::Lifetime(lifetime.clone()));
+
+                    has_lifetime = true;
+                }
+
+                borrows |= has_lifetime;
+            }
+
+            borrows
+        }
+        syn::Type::Tuple(tuple) => tuple
+            .elems
+            .iter_mut()
+            .fold(false, |borrows, elem| borrow_for(elem, lifetime) | borrows),
+        syn::Type::Array(syn::TypeArray { elem, .. })
+        | syn::Type::Slice(syn::TypeSlice { elem, .. })
+        | syn::Type::Paren(syn::TypeParen { elem, .. })
+        | syn::Type::Group(syn::TypeGroup { elem, .. }) => borrow_for(elem, lifetime),
+        _ => false,
+    }
+}
+
+/// The items describing a form, `Form`, `FORM`, `Values` and `Errors`, with the given visibility.
+///
+/// Actions also have an `action` field in `Form`, and the ids of their fields are prefixed by `id_prefix`.
+fn form_items(
+    vis: &proc_macro2::TokenStream,
+    action: Option<String>,
+    id_prefix: Option<&str>,
+    inputs: &[FormInput],
+) -> proc_macro2::TokenStream {
+    let form_metadata = inputs
+        .iter()
+        .flat_map(|input| input.form_metadata(id_prefix))
+        .collect::<Vec<_>>();
+
+    let form_struct_field_definitions = form_metadata
+        .iter()
+        .map(|FormMetadata { ident, ty, .. }| quote::quote! { #vis #ident: #ty });
+
+    let form_struct_field_declarations = form_metadata
+        .iter()
+        .map(|FormMetadata { ident, value, .. }| quote::quote! { #ident: #value });
+
+    let (action_definition, action_declaration) = action
+        .map(|action| {
+            (
+                quote::quote! { #vis action: &'static str, },
+                quote::quote! { action: #action, },
+            )
+        })
+        .unzip();
+
+    let root = match id_prefix {
+        Some(id) => quote::quote! { html_form_actions::NamePrefix::Form { id: #id } },
+        None => quote::quote! { html_form_actions::NamePrefix::Root },
+    };
+
+    let form_names_methods = inputs
+        .iter()
+        .filter_map(|input| input.form_names_method(vis, &root));
+
+    let input_idents = inputs.iter().map(FormInput::ident).collect::<Vec<_>>();
+
+    let values_types = inputs
+        .iter()
+        .map(FormInput::values_type)
+        .collect::<Vec<_>>();
+
+    let static_lifetime = syn::Lifetime::new("'static", proc_macro2::Span::call_site());
+    let r_lifetime = syn::Lifetime::new("'r", proc_macro2::Span::call_site());
+
+    let errors_types = inputs
+        .iter()
+        .map(|input| input.errors_type(&static_lifetime));
+
+    // The bounds must depend on a lifetime, otherwise those which don't hold are an error
+    let errors_copy_bounds = inputs.iter().map(|input| input.errors_type(&r_lifetime));
+
+    let prefill_types = inputs
+        .iter()
+        .map(FormInput::prefill_type)
+        .collect::<Vec<_>>();
+
+    let prefill_values = inputs.iter().map(FormInput::prefill);
+
+    let prefill_marker = inputs.is_empty().then(|| {
+        quote::quote! { #vis _marker: core::marker::PhantomData<&'a str>, }
+    });
+
+    let prefill_marker_value = inputs.is_empty().then(|| {
+        quote::quote! { _marker: core::marker::PhantomData, }
+    });
+
+    let visit_pairs = inputs
+        .iter()
+        .map(|input| input.visit_pairs(&quote::quote! { FORM }));
+
+    let render_inputs = inputs
+        .iter()
+        .map(|input| input.render(&quote::quote! { FORM }));
+
+    let typed_params = inputs
+        .iter()
+        .map(FormInput::typed_param)
+        .collect::<Vec<_>>();
+
+    let typed_bounds = inputs.iter().map(FormInput::typed_bound);
+
+    let visit_typed_pairs = inputs
+        .iter()
+        .map(|input| input.visit_typed_pairs(&quote::quote! { FORM }));
+
+    let values_marker = inputs.is_empty().then(|| {
+        quote::quote! { #vis _marker: core::marker::PhantomData<&'r str>, }
+    });
+
+    quote::quote! {
+        #vis struct Form {
+            #action_definition
+            #(#form_struct_field_definitions,)*
+        }
+
+        #vis const FORM: Form = Form {
+            #action_declaration
+            #(#form_struct_field_declarations,)*
+        };
+
+        impl Form {
+            #(#form_names_methods)*
+        }
+
+        #[derive(Debug, Clone, Default)]
+        #vis struct Values<'r> {
+            #(#vis #input_idents: #values_types,)*
+            #values_marker
+        }
+
+        // Multi-valued and nested fields may not be `Copy`
+        impl<'r> Copy for Values<'r> where #(#values_types: Copy,)* {}
+
+        #[derive(Debug, Clone, Default, PartialEq, Eq)]
+        #vis struct Errors {
+            #(#vis #input_idents: #errors_types,)*
+        }
+
+        impl<'r> Copy for Errors where #(#errors_copy_bounds: Copy,)* {}
+
+        /// The values used to prefill the HTML inputs, such as the current values on an edit page,
+        /// or the submitted values of a rejected submission.
+        #[derive(Debug, Clone, Default)]
+        #vis struct Prefill<'a> {
+            #(#vis #input_idents: #prefill_types,)*
+            #prefill_marker
+        }
+
+        // Nested lists are not `Copy`
+        impl<'a> Copy for Prefill<'a> where #(#prefill_types: Copy,)* {}
+
+        impl<'a, 'r> From<&'a Values<'r>> for Prefill<'a> {
+            fn from(values: &'a Values<'r>) -> Self {
+                Self {
+                    #(#input_idents: #prefill_values,)*
+                    #prefill_marker_value
+                }
+            }
+        }
+
+        impl Values<'_> {
+            /// The values, encoded as `application/x-www-form-urlencoded` while formatting.
+            #vis fn to_urlencoded(&self) -> html_form_actions::urlencoded::UrlEncoded<'_, Self> {
+                html_form_actions::urlencoded::UrlEncoded {
+                    values: self,
+                    visit_pairs,
+                }
+            }
+        }
+
+        /// Call `visit` with the name and value of each submitted field, with the given prefix.
+        #vis fn visit_pairs(
+            prefix: html_form_actions::NamePrefix<'_>,
+            values: &Values<'_>,
+            visit: &mut dyn FnMut(html_form_actions::FieldName<'_>, html_form_actions::FormValue<'_>) -> core::ops::ControlFlow<()>,
+        ) -> core::ops::ControlFlow<()> {
+            #(#visit_pairs)*
+
+            core::ops::ControlFlow::Continue(())
+        }
+
+        /// The typed values of each field, such as to submit the form from a test,
+        /// which may be of any types which can be written as the values they would be submitted as.
+        #[derive(Debug, Clone, Copy, Default)]
+        #vis struct Typed<#(#typed_params,)*> {
+            #(#vis #input_idents: #typed_params,)*
+        }
+
+        impl<#(#typed_bounds,)*> html_form_actions::ToHtmlForm for Typed<#(#typed_params,)*> {
+            fn visit_pairs(
+                &self,
+                prefix: html_form_actions::NamePrefix<'_>,
+                visit: &mut dyn FnMut(html_form_actions::FieldName<'_>, html_form_actions::FormValue<'_>) -> core::ops::ControlFlow<()>,
+            ) -> core::ops::ControlFlow<()> {
+                #(#visit_typed_pairs)*
+
+                core::ops::ControlFlow::Continue(())
+            }
+        }
+
+        /// Render each field as HTML with the given prefix, prefilled with `values` and showing `errors`.
+        #vis fn render_fields(
+            f: &mut core::fmt::Formatter<'_>,
+            prefix: html_form_actions::NamePrefix<'_>,
+            values: &Values<'_>,
+            errors: &Errors,
+        ) -> core::fmt::Result {
+            #(#render_inputs)*
+
+            Ok(())
+        }
+    }
+}
+
+/// Statements which parse the form inputs from `values` into `Option`s with the same name as each input,
+/// recording any errors into `errors`.
+fn parse_inputs(inputs: &[FormInput]) -> proc_macro2::TokenStream {
+    let parse_inputs = inputs.iter().map(FormInput::parse);
+
+    quote::quote! { #(#parse_inputs)* }
+}
+
+/// An expression evaluating to the number of fields in a [`Rejection`] of the form inputs.
+fn rejected_field_count(inputs: &[FormInput]) -> proc_macro2::TokenStream {
+    if inputs.is_empty() {
+        return quote::quote! { 0 };
+    }
+
+    let field_counts = inputs.iter().map(FormInput::field_count);
+
+    quote::quote! { #(#field_counts)+* }
+}
+
+/// An iterator over the fields of a [`Rejection`] of the form inputs.
+fn rejected_fields(
+    form: &proc_macro2::TokenStream,
+    inputs: &[FormInput],
+) -> proc_macro2::TokenStream {
+    let rejected_fields = inputs.iter().map(|input| input.rejected_fields(form));
+
+    quote::quote! { core::iter::empty() #(.chain(#rejected_fields))* }
+}
+
+struct ActionArgument {
+    ident: syn::Ident,
+    ty: syn::Type,
+}
+
+struct Action {
+    ident: syn::Ident,
+    fail: Option<syn::Path>,
+    form: Vec<FormInput>,
+    other_arguments: Vec<ActionArgument>,
+    call_arguments: Vec<proc_macro2::TokenStream>,
+}
+
+impl Action {
+    fn extract(items: &mut [syn::Item]) -> syn::Result<Vec<Self>> {
+        #[derive(deluxe::ExtractAttributes)]
+        #[deluxe(attributes(action))]
+        struct ActionAttribute {
+            #[deluxe(default)]
+            fail: Option<syn::Path>,
+        }
+
+        let mut actions = Vec::new();
+
+        for item in items {
+            let syn::Item::Fn(f) = item else {
+                continue;
+            };
+
+            let Some(ActionAttribute { fail }) = maybe_extract_attributes(f)? else {
+                continue;
+            };
+
+            let mut form = Vec::new();
+            let mut other_arguments = Vec::new();
+            let mut call_arguments = Vec::new();
+
+            for (index, input) in f.sig.inputs.iter_mut().enumerate() {
+                match input {
+                    syn::FnArg::Receiver(_) => {
+                        return Err(syn::Error::new(input.span(), r#""self" is not allowed"#));
+                    }
+                    syn::FnArg::Typed(syn::PatType {
+                        attrs,
+                        pat,
+                        colon_token: _,
+                        ty,
+                    }) => {
+                        if let Some(form_attrs) = maybe_extract_attributes(attrs)? {
+                            let syn::Pat::Ident(syn::PatIdent { ident, .. }) = pat.as_ref() else {
+                                return Err(syn::Error::new(
+                                    pat.span(),
+                                    "parameters tagged with #[form] must be identifiers",
+                                ));
+                            };
+
+                            call_arguments.push(quote::quote! { form.#ident });
+
+                            form.push(FormInput::new(
+                                ident.clone(),
+                                ty.as_ref().clone(),
+                                form_attrs,
+                            )?);
+                        } else {
+                            let ident = syn::Ident::new(&format!("arg_{index}"), pat.span());
+
+                            // The other arguments are extracted once, so if the action may fail, it is given a clone of each,
+                            // and the `fail` handler is given the extracted values
+                            call_arguments.push(if fail.is_some() {
+                                quote::quote! { core::clone::Clone::clone(&#ident) }
+                            } else {
+                                ident.to_token_stream()
+                            });
+
+                            other_arguments.push(ActionArgument {
+                                ident,
+                                ty: ty.as_ref().clone(),
+                            });
+                        }
+                    }
+                }
+            }
+
+            actions.push(Action {
+                ident: f.sig.ident.clone(),
+                fail,
+                form,
+                other_arguments,
+                call_arguments,
+            });
+        }
+
+        Ok(actions)
+    }
+
+    /// The name of the action, as used in its query, such as `my_action`, which is `clear` for `r#clear`.
+    fn name(&self) -> String {
+        self.ident.unraw().to_string()
+    }
+
+    fn query(&self) -> String {
+        format!("/{}", self.name())
+    }
+
+    /// Whether any `#[form]` parameter borrows from the request body, so that the parsed form has a lifetime.
+    fn borrows(&self) -> bool {
+        self.form.iter().any(FormInput::borrows)
+    }
+
+    /// Declares `struct Parsed`, containing the parsed form fields, `struct Collected`, which collects the submitted values
+    /// of each field, and `fn parse`, which parses the body into the action's `Values`, and then into `Parsed` or the action's `Errors`.
+    ///
+    /// These are declared within the action's module, and used by each integration and by the parsed action enum.
+    /// Integrations which decode the fields themselves, such as Rocket's, push them to `Collected` rather than calling `parse`.
+    fn parse_declaration(&self) -> proc_macro2::TokenStream {
+        let Self { form, .. } = self;
+
+        let form_fields = form.iter().map(|input| {
+            let ident = input.ident();
+            let ty = input.field_type();
+
+            quote::quote! { pub(super) #ident: #ty }
+        });
+
+        let (parsed_lifetime, parsed_type) = if self.borrows() {
+            (quote::quote! { <'r> }, quote::quote! { Parsed<'r> })
+        } else {
+            (quote::quote! {}, quote::quote! { Parsed })
+        };
+
+        let form_const = quote::quote! { FORM };
+
+        let collect_values = form.iter().map(|input| input.collect_value(&form_const));
+
+        let parse_inputs = parse_inputs(form);
+
+        let field_idents = form.iter().map(FormInput::ident).collect::<Vec<_>>();
+
+        quote::quote! {
+            /// The parsed `#[form]` parameters of the action.
+            pub(super) struct Parsed #parsed_lifetime {
+                #(#form_fields,)*
+            }
+
+            /// The values of each field which have been submitted so far, and the reason any field was rejected while collecting them.
+            #[derive(Default)]
+            pub(super) struct Collected<'r> {
+                values: Values<'r>,
+                errors: Errors,
+            }
+
+            impl<'r> Collected<'r> {
+                /// Collect a decoded `value` of the field called `name`, ignoring fields which aren't part of the form.
+                pub(super) fn push(&mut self, name: &str, value: &'r str) {
+                    let Self { values, errors } = self;
+
+                    #(#collect_values)*
+                }
+
+                /// Parse the collected values into the `Parsed` parameters, or the reason each was rejected.
+                #[allow(clippy::type_complexity)]
+                pub(super) fn finish(self) -> (Values<'r>, Result<#parsed_type, Errors>) {
+                    let Self { values, mut errors } = self;
+
+                    #parse_inputs
+
+                    #[allow(irrefutable_let_patterns)]
+                    let (#(Some(#field_idents),)*) = (#(#field_idents,)*) else {
+                        return (values, Err(errors));
+                    };
+
+                    (values, Ok(Parsed { #(#field_idents,)* }))
+                }
+            }
+
+            /// Parse `body`, which is decoded in place, into the submitted `Values`,
+            /// and then into the `Parsed` parameters, or the reason each was rejected.
+            #[allow(clippy::type_complexity)]
+            pub(super) fn parse<'r>(
+                body: &'r mut [u8],
+            ) -> Result<(Values<'r>, Result<#parsed_type, Errors>), core::str::Utf8Error> {
+                let mut collected = Collected::default();
+
+                for pair in html_form_actions::urlencoded::pairs(body) {
+                    let (name, value) = pair?;
+
+                    collected.push(name, value);
+                }
+
+                Ok(collected.finish())
+            }
+        }
+    }
+
+    /// A [`Rejection`] of the submitted `values`, with the given `errors`.
+    fn rejection(&self) -> proc_macro2::TokenStream {
+        let Self {
+            ident: action_ident,
+            form,
+            ..
+        } = self;
+
+        let form_const = quote::quote! { #action_ident::FORM };
+
+        let field_count = rejected_field_count(form);
+
+        let rejected_fields = rejected_fields(&form_const, form);
+
+        quote::quote! {
+            html_form_actions::Rejection {
+                action: #action_ident::FORM.action,
+                fields: &{
+                    let mut fields = #rejected_fields;
+
+                    core::array::from_fn::<_, { #field_count }, _>(|_| fields.next().unwrap_or_default())
+                },
+            }
+        }
+    }
+}
+
+#[derive(deluxe::ParseMetaItem)]
+struct AxumActionAttributes {
+    #[deluxe(default = syn::Ident::new("actions_handler", proc_macro2::Span::call_site()))]
+    handler: syn::Ident,
+}
+
+#[derive(deluxe::ParseMetaItem)]
+struct ActixWebActionAttributes {
+    #[deluxe(default = syn::Ident::new("actions_handler", proc_macro2::Span::call_site()))]
+    handler: syn::Ident,
+}
+
+#[derive(deluxe::ParseMetaItem)]
+struct RocketActionAttributes {
+    #[deluxe(default = syn::Ident::new("ActionsHandler", proc_macro2::Span::call_site()))]
+    handler: syn::Ident,
+}
+
+#[derive(deluxe::ParseMetaItem)]
+struct PoemActionAttributes {
+    #[deluxe(default = syn::Ident::new("ActionsEndpoint", proc_macro2::Span::call_site()))]
+    handler: syn::Ident,
+    #[deluxe(default)]
+    limit: Option<syn::Expr>,
+}
+
+#[derive(deluxe::ParseMetaItem)]
+struct SalvoActionAttributes {
+    #[deluxe(default = syn::Ident::new("ActionsHandler", proc_macro2::Span::call_site()))]
+    handler: syn::Ident,
+}
+
+#[derive(deluxe::ParseMetaItem)]
+struct PicoserveActionAttributes {
+    #[deluxe(default)]
+    path_parameters: Vec<syn::Type>,
+    #[deluxe(default = syn::Ident::new("ActionsHandler", proc_macro2::Span::call_site()))]
+    handler: syn::Ident,
+}
+
+#[derive(deluxe::ParseMetaItem)]
+struct HttpActionAttributes {
+    #[deluxe(default = syn::Ident::new("actions_handler", proc_macro2::Span::call_site()))]
+    handler: syn::Ident,
+    #[deluxe(default)]
+    limit: Option<syn::Expr>,
+}
+
+#[derive(deluxe::ParseMetaItem)]
+struct EmbeddedIoActionAttributes {
+    #[deluxe(default = syn::Ident::new("actions_handler", proc_macro2::Span::call_site()))]
+    handler: syn::Ident,
+    #[deluxe(default)]
+    capacity: Option<syn::Expr>,
+}
+
+#[derive(deluxe::ParseMetaItem)]
+struct ParseActionAttributes {
+    #[deluxe(default = syn::Ident::new("ParsedAction", proc_macro2::Span::call_site()))]
+    action: syn::Ident,
+    #[deluxe(default = syn::Ident::new("ParseError", proc_macro2::Span::call_site()))]
+    error: syn::Ident,
+}
+
+#[derive(deluxe::ParseMetaItem)]
+struct BackendActionAttributes {
+    with: syn::Type,
+    #[deluxe(default = syn::Ident::new("dispatch_action", proc_macro2::Span::call_site()))]
+    handler: syn::Ident,
+}
+
+mod optional_struct {
+    pub fn parse_meta_item_named<T: deluxe::ParseMetaItem>(
+        input: syn::parse::ParseStream,
+        _name: &str,
+        span: proc_macro2::Span,
+    ) -> deluxe::Result<Option<T>> {
+        deluxe_core::parse_helpers::parse_named_meta_item(input, span).map(Some)
+    }
+}
+
+#[derive(deluxe::ParseMetaItem)]
+struct ActionAttributes {
+    #[deluxe(default)]
+    state: Option<syn::Type>,
+    #[deluxe(default)]
+    reject: Option<syn::Path>,
+    #[deluxe(default, with = optional_struct)]
+    axum: Option<AxumActionAttributes>,
+    #[deluxe(default, with = optional_struct)]
+    actix_web: Option<ActixWebActionAttributes>,
+    #[deluxe(default, with = optional_struct)]
+    rocket: Option<RocketActionAttributes>,
+    #[deluxe(default, with = optional_struct)]
+    poem: Option<PoemActionAttributes>,
+    #[deluxe(default, with = optional_struct)]
+    salvo: Option<SalvoActionAttributes>,
+    #[deluxe(default, with = optional_struct)]
+    picoserve: Option<PicoserveActionAttributes>,
+    #[deluxe(default, with = optional_struct)]
+    http: Option<HttpActionAttributes>,
+    #[deluxe(default, with = optional_struct)]
+    embedded_io: Option<EmbeddedIoActionAttributes>,
+    #[deluxe(default, with = optional_struct)]
+    backend: Option<BackendActionAttributes>,
+    #[deluxe(default, with = optional_struct)]
+    parse: Option<ParseActionAttributes>,
+}
+
+fn axum_handler(
+    state: &Option<syn::Type>,
+    reject: &Option<syn::Path>,
+    AxumActionAttributes { handler }: AxumActionAttributes,
+    actions: &[Action],
+) -> syn::Result<syn::ItemFn> {
+    let state_argument = state.as_ref().map(|state| {
+        quote::quote! {
+            axum::extract::State(state): axum::extract::State<#state>,
+        }
+    });
+
+    let state_value = if state.is_some() {
+        quote::quote! { state }
+    } else {
+        quote::quote! { () }
+    };
+
+    let action_cases = actions.iter().map(
+        |action @ Action {
+             ident,
+             fail,
+             form: _,
+             other_arguments,
+             call_arguments,
+         }| {
+            let query = action.query();
+
+
+            let extract_other_arguments = other_arguments.iter().map(|ActionArgument { ident, ty }| {
+                quote::quote! {
+                    let #ident = match <#ty as FromRequestParts<_>>::from_request_parts(&mut parts, state).await {
+                        Ok(value) => value,
+                        Err(rejection) => return rejection.into_response(),
+                    };
+                }
+            }).collect::<Vec<_>>();
+
+            let other_argument_idents = other_arguments
+                .iter()
+                .map(|ActionArgument { ident, .. }| ident)
+                .collect::<Vec<_>>();
+
+            let (values_pattern, call_action) = match (fail, reject) {
+                (None, None) => (
+                    quote::quote! { _ },
+                    quote::quote! {
+                        match form {
+                            Ok(form) => #ident(#(#call_arguments,)*).await.into_response(),
+                            Err(_) => (axum::http::StatusCode::BAD_REQUEST, "Bad Form").into_response(),
+                        }
+                    },
+                ),
+                (None, Some(reject)) => {
+                    let rejection = action.rejection();
+
+                    (
+                        quote::quote! { values },
+                        quote::quote! {
+                            match form {
+                                Ok(form) => #ident(#(#call_arguments,)*).await.into_response(),
+                                Err(errors) => #reject(#rejection).await.into_response(),
+                            }
+                        },
+                    )
+                }
+                (Some(fail), _) => (
+                    quote::quote! { values },
+                    quote::quote! {
+                        match form {
+                            Ok(form) => match #ident(#(#call_arguments,)*).await {
+                                Ok(response) => response.into_response(),
+                                Err(errors) => #fail(html_form_actions::Failure { values, errors }, #(#other_argument_idents,)*)
+                                    .await
+                                    .into_response(),
+                            },
+                            Err(errors) => #fail(html_form_actions::Failure { values, errors }, #(#other_argument_idents,)*)
+                                .await
+                                .into_response(),
+                        }
+                    },
+                ),
+            };
+
+            quote::quote! {
+                Some(#query) => {
+                    let (mut parts, body) = request.into_parts();
+
+                    let RawForm(body) = match RawForm::from_request(
+                        axum::extract::Request::from_parts(parts.clone(), body),
+                        state,
+                    )
+                    .await
+                    {
+                        Ok(body) => body,
+                        Err(rejection) => return rejection.into_response(),
+                    };
+
+                    let mut body = body.to_vec();
+
+                    let Ok((#values_pattern, form)) = #ident::parse(&mut body) else {
+                        return (axum::http::StatusCode::BAD_REQUEST, "Form is not valid UTF-8").into_response();
+                    };
+
+                    #(#extract_other_arguments)*
+
+                    #call_action
+                },
+            }
+        },
+    );
+
+    Ok(syn::parse_quote! {
+        async fn #handler(
+            #state_argument
+            axum::extract::RawQuery(query): axum::extract::RawQuery,
+            request: axum::extract::Request,
+        ) -> axum::response::Response {
+            use axum::{
+                extract::{FromRequest, FromRequestParts, RawForm},
+                response::IntoResponse,
+            };
+
+            let state = &#state_value;
+
+            match html_form_actions::query_action(query.as_deref()) {
+                #(#action_cases)*
+                _ => (axum::http::StatusCode::NOT_FOUND, "Action Not Found").into_response(),
+            }
+        }
+    })
+}
+
+fn actix_web_handler(
+    reject: &Option<syn::Path>,
+    ActixWebActionAttributes { handler }: ActixWebActionAttributes,
+    actions: &[Action],
+) -> syn::ItemFn {
+    let action_cases = actions.iter().map(
+        |action @ Action {
+             ident,
+             fail,
+             form: _,
+             other_arguments,
+             call_arguments,
+         }| {
+            let query = action.query();
+
+
+            let extract_other_arguments = other_arguments.iter().map(|ActionArgument { ident, ty }| {
+                quote::quote! {
+                    let #ident = match <#ty as FromRequest>::extract(&request).await {
+                        Ok(value) => value,
+                        Err(error) => return actix_web::HttpResponse::from_error(error),
+                    };
+                }
+            }).collect::<Vec<_>>();
+
+            let other_argument_idents = other_arguments
+                .iter()
+                .map(|ActionArgument { ident, .. }| ident)
+                .collect::<Vec<_>>();
+
+            let (values_pattern, call_action) = match (fail, reject) {
+                (None, None) => (
+                    quote::quote! { _ },
+                    quote::quote! {
+                        match form {
+                            Ok(form) => #ident(#(#call_arguments,)*).await.respond_to(&request).map_into_boxed_body(),
+                            Err(_) => actix_web::HttpResponse::BadRequest().body("Bad Form"),
+                        }
+                    },
+                ),
+                (None, Some(reject)) => {
+                    let rejection = action.rejection();
+
+                    (
+                        quote::quote! { values },
+                        quote::quote! {
+                            match form {
+                                Ok(form) => #ident(#(#call_arguments,)*).await.respond_to(&request).map_into_boxed_body(),
+                                Err(errors) => #reject(#rejection).await.respond_to(&request).map_into_boxed_body(),
+                            }
+                        },
+                    )
+                }
+                (Some(fail), _) => (
+                    quote::quote! { values },
+                    quote::quote! {
+                        match form {
+                            Ok(form) => match #ident(#(#call_arguments,)*).await {
+                                Ok(response) => response.respond_to(&request).map_into_boxed_body(),
+                                Err(errors) => #fail(html_form_actions::Failure { values, errors }, #(#other_argument_idents,)*)
+                                    .await
+                                    .respond_to(&request)
+                                    .map_into_boxed_body(),
+                            },
+                            Err(errors) => #fail(html_form_actions::Failure { values, errors }, #(#other_argument_idents,)*)
+                                .await
+                                .respond_to(&request)
+                                .map_into_boxed_body(),
+                        }
+                    },
+                ),
+            };
+
+            quote::quote! {
+                Some(#query) => {
+                    let mut body = body.to_vec();
+
+                    let Ok((#values_pattern, form)) = #ident::parse(&mut body) else {
+                        return actix_web::HttpResponse::BadRequest().body("Form is not valid UTF-8");
+                    };
+
+                    #(#extract_other_arguments)*
+
+                    #call_action
+                },
+            }
+        },
+    );
+
+    syn::parse_quote! {
+        async fn #handler(
+            request: actix_web::HttpRequest,
+            body: actix_web::web::Bytes,
+        ) -> actix_web::HttpResponse {
+            use actix_web::{FromRequest, Responder};
+
+            match html_form_actions::query_action(Some(request.query_string())) {
+                #(#action_cases)*
+                _ => actix_web::HttpResponse::NotFound().body("Action Not Found"),
+            }
+        }
+    }
+}
+
+fn rocket_handler(
+    reject: &Option<syn::Path>,
+    RocketActionAttributes { handler }: RocketActionAttributes,
+    actions: &[Action],
+) -> Vec<syn::Item> {
+    let action_cases = actions.iter().map(
+        |action @ Action {
+             ident,
+             fail,
+             form: _,
+             other_arguments,
+             call_arguments,
+         }| {
+            let query = action.query();
+
+
+            // Guards are run before the body is read, so that a guard can forward the request, with its data, to another route
+            let extract_guards = other_arguments.iter().map(|ActionArgument { ident, ty }| {
+                quote::quote! {
+                    let #ident = match <#ty as rocket::request::FromRequest<'r>>::from_request(request).await {
+                        rocket::request::Outcome::Success(value) => value,
+                        rocket::request::Outcome::Error((status, _)) => return Outcome::error(status),
+                        rocket::request::Outcome::Forward(status) => return Outcome::forward(data, status),
+                    };
+                }
+            }).collect::<Vec<_>>();
+
+            let other_argument_idents = other_arguments
+                .iter()
+                .map(|ActionArgument { ident, .. }| ident)
+                .collect::<Vec<_>>();
+
+            let (values_pattern, call_action) = match (fail, reject) {
+                (None, None) => (
+                    quote::quote! { _ },
+                    quote::quote! {
+                        match form {
+                            Ok(form) => Outcome::from(request, #ident(#(#call_arguments,)*).await),
+                            Err(_) => Outcome::from(request, (Status::BadRequest, "Bad Form")),
+                        }
+                    },
+                ),
+                (None, Some(reject)) => {
+                    let rejection = action.rejection();
+
+                    (
+                        quote::quote! { values },
+                        quote::quote! {
+                            match form {
+                                Ok(form) => Outcome::from(request, #ident(#(#call_arguments,)*).await),
+                                Err(errors) => Outcome::from(request, #reject(#rejection).await),
+                            }
+                        },
+                    )
+                }
+                (Some(fail), _) => (
+                    quote::quote! { values },
+                    quote::quote! {
+                        match form {
+                            Ok(form) => match #ident(#(#call_arguments,)*).await {
+                                Ok(response) => Outcome::from(request, response),
+                                Err(errors) => Outcome::from(
+                                    request,
+                                    #fail(html_form_actions::Failure { values, errors }, #(#other_argument_idents,)*).await,
+                                ),
+                            },
+                            Err(errors) => Outcome::from(
+                                request,
+                                #fail(html_form_actions::Failure { values, errors }, #(#other_argument_idents,)*).await,
+                            ),
+                        }
+                    },
+                ),
+            };
+
+            quote::quote! {
+                Some(#query) => {
+                    #(#extract_guards)*
+
+                    // The body is decoded by Rocket, which forwards requests which aren't forms
+                    let collected = match <Form<#ident::Collected<'r>> as FromData<'r>>::from_data(request, data).await {
+                        rocket::outcome::Outcome::Success(form) => form.into_inner(),
+                        rocket::outcome::Outcome::Error((status, _)) => return Outcome::error(status),
+                        rocket::outcome::Outcome::Forward((data, status)) => return Outcome::forward(data, status),
+                    };
+
+                    let (#values_pattern, form) = collected.finish();
+
+                    #call_action
+                },
+            }
+        },
+    );
+
+    // The `#[form]` parameters are collected from the fields decoded by Rocket, so that rejected fields are passed to the handler,
+    // whereas errors from decoding the body, such as exceeding the `form` limit, fail the request
+    let from_form_impls = actions.iter().map(|Action { ident, .. }| -> syn::Item {
+        syn::parse_quote! {
+            #[rocket::async_trait]
+            impl<'r> rocket::form::FromForm<'r> for #ident::Collected<'r> {
+                type Context = (Self, rocket::form::Errors<'r>);
+
+                fn init(_options: rocket::form::Options) -> Self::Context {
+                    (<Self as Default>::default(), rocket::form::Errors::new())
+                }
+
+                fn push_value((collected, _): &mut Self::Context, field: rocket::form::ValueField<'r>) {
+                    collected.push(field.name.source().as_str(), field.value);
+                }
+
+                async fn push_data(_context: &mut Self::Context, _field: rocket::form::DataField<'r, '_>) {}
+
+                fn push_error((_, errors): &mut Self::Context, error: rocket::form::Error<'r>) {
+                    errors.push(error);
+                }
+
+                fn finalize((collected, errors): Self::Context) -> rocket::form::Result<'r, Self> {
+                    if errors.is_empty() {
+                        Ok(collected)
+                    } else {
+                        Err(errors)
+                    }
+                }
+            }
+        }
+    });
+
+    let handler_items: [syn::Item; 3] = [
+        syn::parse_quote! {
+            #[derive(Clone, Copy)]
+            struct #handler;
+        },
+        syn::parse_quote! {
+            #[rocket::async_trait]
+            impl rocket::route::Handler for #handler {
+                async fn handle<'r>(
+                    &self,
+                    request: &'r rocket::Request<'_>,
+                    data: rocket::Data<'r>,
+                ) -> rocket::route::Outcome<'r> {
+                    use rocket::{data::FromData, form::Form, http::Status, route::Outcome};
+
+                    match html_form_actions::query_action(request.uri().query().map(|query| query.as_str())) {
+                        #(#action_cases)*
+                        _ => Outcome::from(request, (Status::NotFound, "Action Not Found")),
+                    }
+                }
+            }
+        },
+        syn::parse_quote! {
+            /// Mount the handler at the path of the page, such as `rocket.mount("/page", ActionsHandler)`.
+            impl From<#handler> for Vec<rocket::Route> {
+                fn from(handler: #handler) -> Self {
+                    Vec::from([rocket::Route::new(rocket::http::Method::Post, "/", handler)])
+                }
+            }
+        },
+    ];
+
+    handler_items.into_iter().chain(from_form_impls).collect()
+}
+
+fn poem_handler(
+    reject: &Option<syn::Path>,
+    PoemActionAttributes { handler, limit }: PoemActionAttributes,
+    actions: &[Action],
+) -> [syn::Item; 2] {
+    // The same default as the `http` integration, as poem doesn't limit the body itself
+    let limit = match limit {
+        Some(limit) => quote::quote! { #limit },
+        None => quote::quote! { 2 * 1024 * 1024 },
+    };
+
+    let action_cases = actions.iter().map(
+        |action @ Action {
+             ident,
+             fail,
+             form: _,
+             other_arguments,
+             call_arguments,
+         }| {
+            let query = action.query();
+
+
+            let extract_other_arguments = other_arguments.iter().map(|ActionArgument { ident, ty }| {
+                quote::quote! {
+                    let #ident = <#ty as FromRequest>::from_request_without_body(&request).await?;
+                }
+            }).collect::<Vec<_>>();
 
-            let mut form = Vec::new();
-            let mut other_arguments = Vec::new();
+            let other_argument_idents = other_arguments
+                .iter()
+                .map(|ActionArgument { ident, .. }| ident)
+                .collect::<Vec<_>>();
 
-            for (index, input) in f.sig.inputs.iter_mut().enumerate() {
-                match input {
-                    syn::FnArg::Receiver(_) => {
-                        return Err(syn::Error::new(input.span(), r#""self" is not allowed"#));
-                    }
-                    syn::FnArg::Typed(syn::PatType {
-                        attrs,
-                        pat,
-                        colon_token: _,
-                        ty,
-                    }) => {
-                        #[derive(deluxe::ExtractAttributes)]
-                        struct FormAttrs {
-                            #[deluxe(default)]
-                            rename: Option<syn::Expr>,
+            let (values_pattern, call_action) = match (fail, reject) {
+                (None, None) => (
+                    quote::quote! { _ },
+                    quote::quote! {
+                        match form {
+                            Ok(form) => #ident(#(#call_arguments,)*).await.into_response(),
+                            Err(_) => (StatusCode::BAD_REQUEST, "Bad Form").into_response(),
                         }
-
-                        fn pat_ident(pat: &syn::Pat) -> Option<syn::Ident> {
-                            if let syn::Pat::Ident(syn::PatIdent { ident, .. }) = pat {
-                                Some(ident.clone())
-                            } else {
-                                None
+                    },
+                ),
+                (None, Some(reject)) => {
+                    let rejection = action.rejection();
+
+                    (
+                        quote::quote! { values },
+                        quote::quote! {
+                            match form {
+                                Ok(form) => #ident(#(#call_arguments,)*).await.into_response(),
+                                Err(errors) => #reject(#rejection).await.into_response(),
                             }
+                        },
+                    )
+                }
+                (Some(fail), _) => (
+                    quote::quote! { values },
+                    quote::quote! {
+                        match form {
+                            Ok(form) => match #ident(#(#call_arguments,)*).await {
+                                Ok(response) => response.into_response(),
+                                Err(errors) => #fail(html_form_actions::Failure { values, errors }, #(#other_argument_idents,)*)
+                                    .await
+                                    .into_response(),
+                            },
+                            Err(errors) => #fail(html_form_actions::Failure { values, errors }, #(#other_argument_idents,)*)
+                                .await
+                                .into_response(),
                         }
+                    },
+                ),
+            };
 
-                        if let Some(FormAttrs { rename }) = maybe_extract_attributes(attrs)? {
-                            let ident = pat_ident(pat).ok_or_else(|| {
-                                syn::Error::new(
-                                    pat.span(),
-                                    "parameters tagged with #[form] must be identifiers",
-                                )
-                            })?;
+            quote::quote! {
+                Some(#query) => {
+                    let mut body = body.into_bytes_limit(#limit).await?.to_vec();
 
-                            let form_name = syn::Ident::new(&format!("{ident}_name"), ident.span());
+                    let Ok((#values_pattern, form)) = #ident::parse(&mut body) else {
+                        return Ok((StatusCode::BAD_REQUEST, "Form is not valid UTF-8").into_response());
+                    };
 
-                            form.push(ActionFormInput {
-                                ident,
-                                rename,
-                                form_name,
-                                ty: ty.as_ref().clone(),
-                            });
-                        } else {
-                            other_arguments.push(pat_ident(pat).unwrap_or_else(|| {
-                                syn::Ident::new(&format!("arg_{index}"), pat.span())
-                            }))
-                        }
-                    }
-                }
+                    #(#extract_other_arguments)*
+
+                    Ok(#call_action)
+                },
             }
+        },
+    );
 
-            actions.push(Action {
-                ident: f.sig.ident.clone(),
-                form,
-                other_arguments,
-            });
-        }
+    [
+        syn::parse_quote! {
+            struct #handler;
+        },
+        syn::parse_quote! {
+            impl poem::Endpoint for #handler {
+                type Output = poem::Response;
 
-        Ok(actions)
-    }
+                async fn call(&self, mut request: poem::Request) -> poem::Result<poem::Response> {
+                    use poem::{FromRequest, IntoResponse, http::StatusCode};
 
-    fn query(&self) -> String {
-        format!("/{}", self.ident)
-    }
-
-    fn struct_declaration(&self) -> proc_macro2::TokenStream {
-        let form_fields = self.form.iter().map(
-            |ActionFormInput {
-                 ident,
-                 rename,
-                 form_name: _,
-                 ty,
-             }| {
-                let rename = rename
-                    .as_ref()
-                    .map(|name| quote::quote! { #[serde(rename = #name)] });
-
-                quote::quote! { #rename #ident: #ty }
-            },
-        );
+                    let body = request.take_body();
 
-        quote::quote! {
-            #[derive(serde::Deserialize)]
-            struct Form {
-                #(#form_fields,)*
+                    match html_form_actions::query_action(request.uri().query()) {
+                        #(#action_cases)*
+                        _ => Ok((StatusCode::NOT_FOUND, "Action Not Found").into_response()),
+                    }
+                }
             }
-        }
-    }
-}
-
-#[derive(deluxe::ParseMetaItem)]
-struct AxumActionAttributes {
-    #[deluxe(default = syn::Ident::new("actions_handler", proc_macro2::Span::call_site()))]
-    handler: syn::Ident,
+        },
+    ]
 }
 
-#[derive(deluxe::ParseMetaItem)]
-struct PicoserveActionAttributes {
-    #[deluxe(default)]
-    path_parameters: Vec<syn::Type>,
-    #[deluxe(default = syn::Ident::new("ActionsHandler", proc_macro2::Span::call_site()))]
-    handler: syn::Ident,
-}
+/// Whether `ty` is `&Depot`, which salvo handlers take to access the injected state.
+fn is_salvo_depot(ty: &syn::Type) -> bool {
+    let syn::Type::Reference(syn::TypeReference {
+        mutability: None,
+        elem,
+        ..
+    }) = ty
+    else {
+        return false;
+    };
 
-mod optional_struct {
-    pub fn parse_meta_item_named<T: deluxe::ParseMetaItem>(
-        input: syn::parse::ParseStream,
-        _name: &str,
-        span: proc_macro2::Span,
-    ) -> deluxe::Result<Option<T>> {
-        deluxe_core::parse_helpers::parse_named_meta_item(input, span).map(Some)
-    }
-}
+    let syn::Type::Path(syn::TypePath { qself: None, path }) = elem.as_ref() else {
+        return false;
+    };
 
-#[derive(deluxe::ParseMetaItem)]
-struct ActionAttributes {
-    #[deluxe(default)]
-    state: Option<syn::Type>,
-    #[deluxe(default, with = optional_struct)]
-    axum: Option<AxumActionAttributes>,
-    #[deluxe(default, with = optional_struct)]
-    picoserve: Option<PicoserveActionAttributes>,
+    path.segments
+        .last()
+        .is_some_and(|segment| segment.ident == "Depot" && segment.arguments.is_none())
 }
 
-fn axum_handler(
-    state: &Option<syn::Type>,
-    AxumActionAttributes { handler }: AxumActionAttributes,
+fn salvo_handler(
+    reject: &Option<syn::Path>,
+    SalvoActionAttributes { handler }: SalvoActionAttributes,
     actions: &[Action],
-) -> syn::Result<syn::ItemFn> {
-    let state_argument = state.as_ref().map(|state| {
-        quote::quote! {
-            axum::extract::State(state): axum::extract::State<#state>,
-        }
-    });
-
+) -> [syn::Item; 2] {
     let action_cases = actions.iter().map(
         |action @ Action {
              ident,
-             form,
+             fail,
+             form: _,
              other_arguments,
+             call_arguments,
          }| {
             let query = action.query();
 
-            let struct_declaration = action.struct_declaration();
 
-            let form_field_names = form
+            let extract_other_arguments = other_arguments
+                .iter()
+                .filter(|ActionArgument { ty, .. }| !is_salvo_depot(ty))
+                .map(|ActionArgument { ident, ty }| {
+                    quote::quote! {
+                        let #ident = match <#ty as salvo::Extractible<'_>>::extract(req, depot).await {
+                            Ok(value) => value,
+                            Err(error) => {
+                                res.status_code(StatusCode::BAD_REQUEST);
+                                return error.write(req, depot, res).await;
+                            }
+                        };
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            // The depot is borrowed by the action, so is reborrowed after the other parameters have been extracted
+            let borrow_depot = other_arguments
+                .iter()
+                .filter(|ActionArgument { ty, .. }| is_salvo_depot(ty))
+                .map(|ActionArgument { ident, .. }| {
+                    quote::quote! { let #ident = &*depot; }
+                })
+                .collect::<Vec<_>>();
+
+            let other_argument_idents = other_arguments
                 .iter()
-                .map(|ActionFormInput { ident, .. }| ident)
+                .map(|ActionArgument { ident, .. }| ident)
                 .collect::<Vec<_>>();
 
-            let action_call = quote::quote! {
-                |#(#other_arguments,)* Form(Form { #(#form_field_names,)* })| async move {
-                    #ident ( #(#form_field_names,)* #(#other_arguments,)*).await.into_response()
+            let (values_pattern, call_action) = match (fail, reject) {
+                (None, None) => (
+                    quote::quote! { _ },
+                    quote::quote! {
+                        match form {
+                            Ok(form) => {
+                                let response = {
+                                    #(#borrow_depot)*
+
+                                    #ident(#(#call_arguments,)*).await
+                                };
+
+                                response.write(req, depot, res).await
+                            }
+                            Err(_) => res.status_code(StatusCode::BAD_REQUEST).render("Bad Form"),
+                        }
+                    },
+                ),
+                (None, Some(reject)) => {
+                    let rejection = action.rejection();
+
+                    (
+                        quote::quote! { values },
+                        quote::quote! {
+                            match form {
+                                Ok(form) => {
+                                    let response = {
+                                        #(#borrow_depot)*
+
+                                        #ident(#(#call_arguments,)*).await
+                                    };
+
+                                    response.write(req, depot, res).await
+                                }
+                                Err(errors) => #reject(#rejection).await.write(req, depot, res).await,
+                            }
+                        },
+                    )
                 }
-            };
+                (Some(fail), _) => (
+                    quote::quote! { values },
+                    quote::quote! {
+                        let result = match form {
+                            Ok(form) => {
+                                #(#borrow_depot)*
+
+                                #ident(#(#call_arguments,)*).await
+                            }
+                            Err(errors) => Err(errors),
+                        };
+
+                        match result {
+                            Ok(response) => response.write(req, depot, res).await,
+                            Err(errors) => {
+                                let response = {
+                                    #(#borrow_depot)*
 
-            let state_value = if state.is_some() {
-                quote::quote! { state }
-            } else {
-                quote::quote! { () }
+                                    #fail(html_form_actions::Failure { values, errors }, #(#other_argument_idents,)*).await
+                                };
+
+                                response.write(req, depot, res).await
+                            }
+                        }
+                    },
+                ),
             };
 
             quote::quote! {
                 Some(#query) => {
-                    #struct_declaration
+                    let mut body = match req.payload().await {
+                        Ok(body) => body.to_vec(),
+                        Err(error) => {
+                            res.status_code(StatusCode::BAD_REQUEST);
+                            return error.write(req, depot, res).await;
+                        }
+                    };
 
-                    Handler::call(
-                        #action_call,
-                        request,
-                        #state_value,
-                    )
-                    .await
+                    let Ok((#values_pattern, form)) = #ident::parse(&mut body) else {
+                        return res.status_code(StatusCode::BAD_REQUEST).render("Form is not valid UTF-8");
+                    };
+
+                    #(#extract_other_arguments)*
+
+                    #call_action
                 },
             }
         },
     );
 
-    Ok(syn::parse_quote! {
-        async fn #handler(
-            #state_argument
-            axum::extract::RawQuery(query): axum::extract::RawQuery,
-            request: axum::extract::Request,
-        ) -> axum::response::Response {
-            use axum::{extract::Form, handler::Handler, response::IntoResponse};
-
-            match html_form_actions::query_action(query.as_deref()) {
-                #(#action_cases)*
-                _ => (axum::http::StatusCode::NOT_FOUND, "Action Not Found").into_response(),
+    [
+        syn::parse_quote! {
+            struct #handler;
+        },
+        syn::parse_quote! {
+            #[salvo::async_trait]
+            impl salvo::Handler for #handler {
+                async fn handle(
+                    &self,
+                    req: &mut salvo::Request,
+                    depot: &mut salvo::Depot,
+                    res: &mut salvo::Response,
+                    _ctrl: &mut salvo::FlowCtrl,
+                ) {
+                    use salvo::{Writer, http::StatusCode};
+
+                    match html_form_actions::query_action(req.uri().query()) {
+                        #(#action_cases)*
+                        _ => res.status_code(StatusCode::NOT_FOUND).render("Action Not Found"),
+                    }
+                }
             }
-        }
-    })
+        },
+    ]
 }
 
 fn picoserve_handler(
     state: &Option<syn::Type>,
+    reject: &Option<syn::Path>,
     PicoserveActionAttributes {
         path_parameters,
         handler,
     }: PicoserveActionAttributes,
     actions: &[Action],
-) -> (syn::ItemStruct, syn::ItemImpl) {
+) -> syn::Result<(syn::ItemStruct, syn::ItemImpl)> {
     let generic_state_name = quote::quote! {State};
 
     let state_generics = state
@@ -260,81 +2247,400 @@ fn picoserve_handler(
         .map(|(index, ty)| syn::Ident::new(&format!("path_parameter_{index}"), ty.span()))
         .collect::<Vec<_>>();
 
+    let path_parameter_value = match path_parameter_names.as_slice() {
+        [] => None,
+        [name] => Some(quote::quote! { #name }),
+        list => Some(quote::quote! { (#(#list,)*) }),
+    };
+
     let action_cases = actions.iter().map(
         |action @ Action {
              ident,
-             form,
+             fail,
+             form: _,
+             other_arguments,
+             call_arguments,
+         }| {
+            let query = action.query();
+
+
+            let (path_parameter_argument, request_parts_arguments) = match path_parameter_names.as_slice() {
+                [] => (None, other_arguments.as_slice()),
+                _ => match other_arguments.split_first() {
+                    Some((first, rest)) => (Some(first), rest),
+                    None => {
+                        return Err(syn::Error::new(
+                            ident.span(),
+                            "actions must take the path parameters as their first non-form parameter",
+                        ));
+                    }
+                },
+            };
+
+            let extract_path_parameters = path_parameter_argument.map(|ActionArgument { ident, ty }| {
+                quote::quote! { let #ident: #ty = #path_parameter_value; }
+            });
+
+            let extract_request_parts_arguments = request_parts_arguments.iter().map(|ActionArgument { ident, ty }| {
+                quote::quote! {
+                    let #ident = match <#ty as picoserve::extract::FromRequestParts<'_, #state>>::from_request_parts(state, &request.parts).await {
+                        Ok(value) => value,
+                        Err(rejection) => return rejection.write_to(request.body_connection.finalize().await?, response_writer).await,
+                    };
+                }
+            }).collect::<Vec<_>>();
+
+            let other_argument_idents = other_arguments
+                .iter()
+                .map(|ActionArgument { ident, .. }| ident)
+                .collect::<Vec<_>>();
+
+            // The values borrow the body, so must be dropped before the body connection is finalized
+            let drop_values = quote::quote! {
+                #[allow(dropping_copy_types)]
+                core::mem::drop(values);
+            };
+
+            let (values_pattern, call_action) = match (fail, reject) {
+                (None, None) => (
+                    quote::quote! { _ },
+                    quote::quote! {
+                        match form {
+                            Ok(form) => #ident(#(#call_arguments,)*)
+                                .await
+                                .write_to_with_state(state, request.body_connection.finalize().await?, response_writer)
+                                .await,
+                            Err(_) => (picoserve::response::StatusCode::BAD_REQUEST, "Bad Form")
+                                .write_to(request.body_connection.finalize().await?, response_writer)
+                                .await,
+                        }
+                    },
+                ),
+                (None, Some(reject)) => {
+                    let rejection = action.rejection();
+
+                    (
+                        quote::quote! { values },
+                        quote::quote! {
+                            match form {
+                                Ok(form) => {
+                                    #drop_values
+
+                                    #ident(#(#call_arguments,)*)
+                                        .await
+                                        .write_to_with_state(state, request.body_connection.finalize().await?, response_writer)
+                                        .await
+                                }
+                                Err(errors) => {
+                                    let response = #reject(#rejection).await;
+
+                                    #drop_values
+
+                                    response
+                                        .write_to_with_state(state, request.body_connection.finalize().await?, response_writer)
+                                        .await
+                                }
+                            }
+                        },
+                    )
+                }
+                (Some(fail), _) => (
+                    quote::quote! { values },
+                    quote::quote! {
+                        match form {
+                            Ok(form) => match #ident(#(#call_arguments,)*).await {
+                                Ok(response) => {
+                                    #drop_values
+
+                                    response
+                                        .write_to_with_state(state, request.body_connection.finalize().await?, response_writer)
+                                        .await
+                                }
+                                Err(errors) => #fail(html_form_actions::Failure { values, errors }, #(#other_argument_idents,)*)
+                                    .await
+                                    .write_to_with_state(state, request.body_connection.finalize().await?, response_writer)
+                                    .await,
+                            },
+                            Err(errors) => #fail(html_form_actions::Failure { values, errors }, #(#other_argument_idents,)*)
+                                .await
+                                .write_to_with_state(state, request.body_connection.finalize().await?, response_writer)
+                                .await,
+                        }
+                    },
+                ),
+            };
+
+            Ok(quote::quote! {
+                Some(query) if query == #query => {
+                    let body = match <&mut [u8] as picoserve::extract::FromRequest<'_, #state>>::from_request(
+                        state,
+                        request.parts,
+                        request.body_connection.body(),
+                    )
+                    .await
+                    {
+                        Ok(body) => body,
+                        Err(rejection) => return rejection.write_to(request.body_connection.finalize().await?, response_writer).await,
+                    };
+
+                    let Ok((#values_pattern, form)) = #ident::parse(body) else {
+                        return (picoserve::response::StatusCode::BAD_REQUEST, "Form is not valid UTF-8")
+                            .write_to(request.body_connection.finalize().await?, response_writer)
+                            .await;
+                    };
+
+                    #extract_path_parameters
+                    #(#extract_request_parts_arguments)*
+
+                    #call_action
+                }
+            })
+        },
+    ).collect::<syn::Result<Vec<_>>>()?;
+
+    let impl_item = syn::parse_quote! {
+        impl #state_generics picoserve::routing::RequestHandlerService<#state, (#(#path_parameters,)*)> for #handler {
+            async fn call_request_handler_service<
+                R: picoserve::io::Read,
+                W: picoserve::response::ResponseWriter<Error = R::Error>,
+            >(
+                &self,
+                state: &#state,
+                (#(#path_parameter_names,)*) : (#(#path_parameters,)*),
+                mut request: picoserve::request::Request<'_, R>,
+                response_writer: W,
+            ) -> Result<picoserve::ResponseSent, W::Error> {
+                use picoserve::response::{IntoResponse, IntoResponseWithState};
+
+                match request.parts.query() {
+                    #(#action_cases)*
+                    _ => {
+                        (
+                            picoserve::response::StatusCode::NOT_FOUND,
+                            "Action Not Found",
+                        )
+                            .write_to(request.body_connection.finalize().await?, response_writer)
+                            .await
+                    }
+                }
+            }
+        }
+    };
+
+    Ok((syn::parse_quote! { struct #handler; }, impl_item))
+}
+
+/// The handler of a module using `backend`, whose request is `request`, with the given `generics`.
+fn backend_handler(
+    reject: &Option<syn::Path>,
+    handler: &syn::Ident,
+    backend: &syn::Type,
+    generics: &syn::Generics,
+    request: &syn::Type,
+    actions: &[Action],
+) -> syn::ItemFn {
+    let into_response = quote::quote! {
+        html_form_actions::IntoActionResponse::<#backend>::into_action_response
+    };
+
+    let action_cases = actions.iter().map(
+        |action @ Action {
+             ident,
+             fail,
+             form: _,
              other_arguments,
+             call_arguments,
          }| {
             let query = action.query();
 
-            let struct_declaration = action.struct_declaration();
 
-            let form_field_names = form
-                .iter()
-                .map(|ActionFormInput { ident, .. }| ident)
-                .collect::<Vec<_>>();
+            let extract_other_arguments = other_arguments.iter().map(|ActionArgument { ident, ty }| {
+                quote::quote! {
+                    let #ident = match <#ty as html_form_actions::FromActionRequest<#backend>>::from_action_request(&mut request).await {
+                        Ok(value) => value,
+                        Err(response) => return response,
+                    };
+                }
+            }).collect::<Vec<_>>();
+
+            let other_argument_idents = other_arguments
+                .iter()
+                .map(|ActionArgument { ident, .. }| ident)
+                .collect::<Vec<_>>();
+
+            let (values_pattern, call_action) = match (fail, reject) {
+                (None, None) => (
+                    quote::quote! { _ },
+                    quote::quote! {
+                        match form {
+                            Ok(form) => #into_response(#ident(#(#call_arguments,)*).await),
+                            Err(_) => <#backend as ActionBackend>::bad_request("Bad Form"),
+                        }
+                    },
+                ),
+                (None, Some(reject)) => {
+                    let rejection = action.rejection();
+
+                    (
+                        quote::quote! { values },
+                        quote::quote! {
+                            match form {
+                                Ok(form) => #into_response(#ident(#(#call_arguments,)*).await),
+                                Err(errors) => #into_response(#reject(#rejection).await),
+                            }
+                        },
+                    )
+                }
+                (Some(fail), _) => (
+                    quote::quote! { values },
+                    quote::quote! {
+                        match form {
+                            Ok(form) => match #ident(#(#call_arguments,)*).await {
+                                Ok(response) => #into_response(response),
+                                Err(errors) => #into_response(
+                                    #fail(html_form_actions::Failure { values, errors }, #(#other_argument_idents,)*).await,
+                                ),
+                            },
+                            Err(errors) => #into_response(
+                                #fail(html_form_actions::Failure { values, errors }, #(#other_argument_idents,)*).await,
+                            ),
+                        }
+                    },
+                ),
+            };
+
+            // The other parameters are extracted first, as the body may borrow the request
+            quote::quote! {
+                Some(#query) => {
+                    #(#extract_other_arguments)*
+
+                    let mut body = match <#backend as ActionBackend>::body(&mut request).await {
+                        Ok(body) => body,
+                        Err(response) => return response,
+                    };
+
+                    let Ok((#values_pattern, form)) = #ident::parse(body.as_mut()) else {
+                        return <#backend as ActionBackend>::bad_request("Form is not valid UTF-8");
+                    };
+
+                    #call_action
+                },
+            }
+        },
+    );
+
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    syn::parse_quote! {
+        async fn #handler #impl_generics (
+            mut request: #request,
+        ) -> <#backend as html_form_actions::ActionBackend>::Response #where_clause {
+            use html_form_actions::ActionBackend;
+
+            match html_form_actions::query_action(<#backend as ActionBackend>::query(&request)) {
+                #(#action_cases)*
+                _ => <#backend as ActionBackend>::not_found(),
+            }
+        }
+    }
+}
+
+/// Declares an enum called `action`, such as `ParsedAction`, with a variant containing the parsed form fields of each action,
+/// an enum called `error`, such as `ParseError`, and a `parse` function, which parses the body of a submission of the named action.
+fn action_enum(
+    ParseActionAttributes {
+        action: action_enum,
+        error: parse_error,
+    }: ParseActionAttributes,
+    actions: &[Action],
+) -> [syn::Item; 3] {
+    let variant_idents = actions
+        .iter()
+        .map(|Action { ident, .. }| pascal_case(ident))
+        .collect::<Vec<_>>();
+
+    let variants = actions
+        .iter()
+        .zip(&variant_idents)
+        .map(|(Action { form, .. }, variant)| {
+            let form_fields = form.iter().map(|input| {
+                let ident = input.ident();
+                let ty = input.field_type();
+
+                quote::quote! { #ident: #ty }
+            });
+
+            quote::quote! { #variant { #(#form_fields,)* } }
+        });
+
+    // The enum only has a lifetime if a parameter borrows from the body, such as `&str`
+    let (action_lifetime, parse_lifetime) = if actions.iter().any(Action::borrows) {
+        (quote::quote! { <'r> }, quote::quote! {})
+    } else {
+        (quote::quote! {}, quote::quote! { <'r> })
+    };
 
-            let action_call = quote::quote! {
-                |#(#other_arguments,)* Form(Form { #(#form_field_names,)* })| async move  {
-                    #ident ( #(#form_field_names,)* #(#other_arguments,)*).await
+    let error_variants =
+        actions
+            .iter()
+            .zip(&variant_idents)
+            .map(|(Action { ident, .. }, variant)| {
+                quote::quote! {
+                    #variant(html_form_actions::Failure<#ident::Values<'r>, #ident::Errors>)
                 }
-            };
+            });
 
-            let path_parameter_list = match path_parameter_names.as_slice() {
-                [] => quote::quote! { picoserve::routing::NoPathParameters },
-                [name] => quote::quote! { picoserve::routing::OnePathParameter(#name) },
-                list => quote::quote! { picoserve::routing::ManyPathParameters((#(#list,)*)) },
-            };
+    let action_cases = actions.iter().zip(&variant_idents).map(|(action, variant)| {
+        let Action { ident, form, .. } = action;
 
-            quote::quote! {
-                Some(query) if query == #query => {
-                    #struct_declaration
+        let name = action.name();
 
-                    picoserve::routing::RequestHandlerFunction::call_handler_func(
-                        &#action_call,
-                        state,
-                        #path_parameter_list,
-                        request,
-                        response_writer,
-                    )
-                    .await
-                }
-            }
-        },
-    );
+        let field_idents = form.iter().map(FormInput::ident).collect::<Vec<_>>();
 
-    let impl_item = syn::parse_quote! {
-        impl #state_generics picoserve::routing::RequestHandlerService<#state, (#(#path_parameters,)*)> for #handler {
-            async fn call_request_handler_service<
-                R: picoserve::io::Read,
-                W: picoserve::response::ResponseWriter<Error = R::Error>,
-            >(
-                &self,
-                state: &#state,
-                (#(#path_parameter_names,)*) : (#(#path_parameters,)*),
-                request: picoserve::request::Request<'_, R>,
-                response_writer: W,
-            ) -> Result<picoserve::ResponseSent, W::Error> {
-                use picoserve::{extract::Form, response::IntoResponse};
+        quote::quote! {
+            #name => match #ident::parse(body).map_err(#parse_error::InvalidUtf8)? {
+                (_, Ok(#ident::Parsed { #(#field_idents,)* })) => Ok(Self::#variant { #(#field_idents,)* }),
+                (values, Err(errors)) => Err(#parse_error::#variant(html_form_actions::Failure { values, errors })),
+            },
+        }
+    });
 
+    let parse_error_doc =
+        format!(" The reason a submission could not be parsed by [`{action_enum}::parse`].");
 
-                match request.parts.query() {
-                    #(#action_cases)*
-                    _ => {
-                        (
-                            picoserve::response::StatusCode::NOT_FOUND,
-                            "Action Not Found",
-                        )
-                            .write_to(request.body_connection.finalize().await?, response_writer)
-                            .await
+    [
+        syn::parse_quote! {
+            /// A parsed submission of one of the actions of the module, containing its `#[form]` parameters.
+            pub(super) enum #action_enum #action_lifetime {
+                #(#variants,)*
+            }
+        },
+        syn::parse_quote! {
+            #[doc = #parse_error_doc]
+            #[derive(Debug)]
+            pub(super) enum #parse_error<'r> {
+                /// The module has no action with the given name.
+                ActionNotFound,
+                /// The body is not valid UTF-8 once decoded.
+                InvalidUtf8(core::str::Utf8Error),
+                #(#error_variants,)*
+            }
+        },
+        syn::parse_quote! {
+            impl #action_lifetime #action_enum #action_lifetime {
+                /// Parse `body` as a submission of the action called `action_name`, such as `my_action`.
+                ///
+                /// The body is decoded in place, so that `&str` parameters borrow from it without an allocator.
+                /// A body which must be kept, such as one which is replayed, can be copied, such as with `&mut body.to_vec()`.
+                pub(super) fn parse #parse_lifetime (action_name: &str, body: &'r mut [u8]) -> Result<Self, #parse_error<'r>> {
+                    match action_name {
+                        #(#action_cases)*
+                        _ => Err(#parse_error::ActionNotFound),
                     }
                 }
             }
-        }
-    };
-
-    (syn::parse_quote! { struct #handler; }, impl_item)
+        },
+    ]
 }
 
 fn try_actions(
@@ -343,10 +2649,82 @@ fn try_actions(
 ) -> syn::Result<proc_macro::TokenStream> {
     let ActionAttributes {
         state,
+        reject,
         axum,
+        actix_web,
+        rocket,
+        poem,
+        salvo,
         picoserve,
+        http,
+        embedded_io,
+        backend,
+        parse,
     } = deluxe::parse(attribute_tokens)?;
 
+    if let Some(state) = &state {
+        // The other integrations extract the state using the other parameters of the actions
+        let ignoring_state = [
+            ("actix_web", actix_web.is_some()),
+            ("rocket", rocket.is_some()),
+            ("poem", poem.is_some()),
+            ("salvo", salvo.is_some()),
+            ("http", http.is_some()),
+            ("embedded_io", embedded_io.is_some()),
+            ("backend", backend.is_some()),
+        ];
+
+        if let Some((option, _)) = ignoring_state
+            .into_iter()
+            .find(|&(_, is_declared)| is_declared)
+        {
+            return Err(syn::Error::new_spanned(
+                state,
+                format!(
+                    "`state` is only used by the `axum` and `picoserve` integrations, and is ignored by `{option}`, whose actions extract the state using their other parameters"
+                ),
+            ));
+        }
+    }
+
+    let handlers = [
+        ("axum", axum.as_ref().map(|axum| &axum.handler)),
+        (
+            "actix_web",
+            actix_web.as_ref().map(|actix_web| &actix_web.handler),
+        ),
+        ("rocket", rocket.as_ref().map(|rocket| &rocket.handler)),
+        ("poem", poem.as_ref().map(|poem| &poem.handler)),
+        ("salvo", salvo.as_ref().map(|salvo| &salvo.handler)),
+        (
+            "picoserve",
+            picoserve.as_ref().map(|picoserve| &picoserve.handler),
+        ),
+        ("http", http.as_ref().map(|http| &http.handler)),
+        (
+            "embedded_io",
+            embedded_io.as_ref().map(|embedded_io| &embedded_io.handler),
+        ),
+        ("backend", backend.as_ref().map(|backend| &backend.handler)),
+    ]
+    .into_iter()
+    .filter_map(|(option, handler)| Some((option, handler?)))
+    .collect::<Vec<_>>();
+
+    for (index, &(option, handler)) in handlers.iter().enumerate() {
+        if let Some(&(other_option, _)) = handlers[..index]
+            .iter()
+            .find(|&&(_, other_handler)| other_handler == handler)
+        {
+            return Err(syn::Error::new(
+                handler.span(),
+                format!(
+                    "the `{option}` handler is called `{handler}`, as is the `{other_option}` handler. Declare a different name using `{option}(handler = ...)`"
+                ),
+            ));
+        }
+    }
+
     let module = syn::parse(tokens)?;
 
     let syn::ItemMod {
@@ -368,44 +2746,99 @@ fn try_actions(
     let actions = Action::extract(&mut items)?;
 
     let action_modules = actions.iter().map(
-        |Action {
-             ident,
+        |action @ Action {
+             ident: action_ident,
+             fail,
              form,
-             other_arguments: _,
+             ..
          }| {
-            let action = format!("?/{ident}");
-
-            let form_struct_field_definitions = form.iter().map(
-                |ActionFormInput { form_name, .. }| quote::quote! { pub(super) #form_name: &'static str },
+            let action_name = action.name();
+            let query = format!("?/{action_name}");
+            let id_prefix = format!("{}-{action_name}", ident.unraw());
+
+            let form_items = form_items(
+                &quote::quote! { pub(in super::super) },
+                Some(query),
+                Some(&id_prefix),
+                form,
             );
 
-            let form_struct_field_declarations = form.iter().map(
-                |ActionFormInput {
-                     ident,
-                     rename,
-                     form_name,
-                     ty: _,
-                 }| {
-                    let name = rename.as_ref().map_or_else(
-                        || ident.to_string().to_token_stream(),
-                        quote::ToTokens::to_token_stream,
-                    );
-
-                    quote::quote! { #form_name: #name }
-                },
-            );
+            let submit = sentence_case(&action_ident.unraw().to_string());
+
+            let typed_params = form
+                .iter()
+                .map(FormInput::typed_param)
+                .collect::<Vec<_>>();
+
+            let typed_bounds = form.iter().map(FormInput::typed_bound);
+
+            let parse_declaration = action.parse_declaration();
+
+            let failure_declaration = fail.is_some().then(|| {
+                quote::quote! {
+                    pub(in super::super) type Failure<'r> = html_form_actions::Failure<Values<'r>, Errors>;
+                }
+            });
 
             syn::Item::Mod(syn::parse_quote! {
-                mod #ident {
-                    pub(super)struct Form {
-                        pub(super) action: &'static str,
-                        #(#form_struct_field_definitions,)*
+                // The module and its items are visible to the parent of the actions module, as is the parsed action enum
+                pub(super) mod #action_ident {
+                    #[allow(unused_imports)]
+                    use super::*;
+
+                    #form_items
+
+                    #parse_declaration
+
+                    /// Render a default form for the action, prefilled with `values` and showing `errors`.
+                    pub(in super::super) fn render<'a, 'r>(
+                        values: &'a Values<'r>,
+                        errors: &'a Errors,
+                    ) -> html_form_actions::DefaultForm<'a, Values<'r>, Errors> {
+                        html_form_actions::DefaultForm {
+                            action: FORM.action,
+                            id_prefix: #id_prefix,
+                            submit: #submit,
+                            values,
+                            errors,
+                            render_fields,
+                        }
                     }
 
-                    pub(super) const FORM: Form = Form {
-                        action: #action,
-                        #(#form_struct_field_declarations,)*
-                    };
+                    pub(in super::super) type Submission<'a, 'r> = html_form_actions::Submission<'a, Values<'r>>;
+
+                    /// A submission of the action with the given `values`.
+                    pub(in super::super) fn submission<'a, 'r>(values: &'a Values<'r>) -> Submission<'a, 'r> {
+                        html_form_actions::Submission {
+                            action: FORM.action,
+                            values,
+                            visit_pairs,
+                        }
+                    }
+
+                    /// A submission of the action with the given `Typed` values.
+                    pub(in super::super) fn typed_submission<'a, #(#typed_bounds,)*>(
+                        values: &'a Typed<#(#typed_params,)*>,
+                    ) -> html_form_actions::Submission<'a, Typed<#(#typed_params,)*>> {
+                        html_form_actions::Submission::typed(FORM.action, values)
+                    }
+
+                    /// The action, used with code which is generic over actions, such as test clients.
+                    pub(in super::super) struct Action;
+
+                    impl html_form_actions::FormAction for Action {
+                        const ACTION: &'static str = FORM.action;
+
+                        type Values<'r> = Values<'r>;
+
+                        fn submission<'a, 'r>(
+                            values: &'a Self::Values<'r>,
+                        ) -> html_form_actions::Submission<'a, Self::Values<'r>> {
+                            submission(values)
+                        }
+                    }
+
+                    #failure_declaration
                 }
             })
         },
@@ -413,16 +2846,86 @@ fn try_actions(
 
     items.extend(action_modules);
 
+    if let Some(parse) = parse {
+        items.extend(action_enum(parse, &actions));
+    }
+
     if let Some(axum) = axum {
-        items.push(syn::Item::Fn(axum_handler(&state, axum, &actions)?));
+        items.push(syn::Item::Fn(axum_handler(
+            &state, &reject, axum, &actions,
+        )?));
+    }
+
+    if let Some(actix_web) = actix_web {
+        items.push(syn::Item::Fn(actix_web_handler(
+            &reject, actix_web, &actions,
+        )));
+    }
+
+    if let Some(rocket) = rocket {
+        items.extend(rocket_handler(&reject, rocket, &actions));
+    }
+
+    if let Some(poem) = poem {
+        items.extend(poem_handler(&reject, poem, &actions));
+    }
+
+    if let Some(salvo) = salvo {
+        items.extend(salvo_handler(&reject, salvo, &actions));
     }
 
     if let Some(picoserve) = picoserve {
-        let (service, service_impl) = picoserve_handler(&state, picoserve, &actions);
+        let (service, service_impl) = picoserve_handler(&state, &reject, picoserve, &actions)?;
 
         items.extend([syn::Item::Struct(service), syn::Item::Impl(service_impl)]);
     }
 
+    if let Some(HttpActionAttributes { handler, limit }) = http {
+        let limit = match limit {
+            Some(limit) => quote::quote! { { #limit } },
+            None => quote::quote! { { html_form_actions::http::DEFAULT_LIMIT } },
+        };
+
+        items.push(syn::Item::Fn(backend_handler(
+            &reject,
+            &handler,
+            &syn::parse_quote! { html_form_actions::http::Http<B, #limit> },
+            &syn::parse_quote! { <B: html_form_actions::http::Body + Unpin> },
+            &syn::parse_quote! { html_form_actions::http::Request<B> },
+            &actions,
+        )));
+    }
+
+    if let Some(EmbeddedIoActionAttributes { handler, capacity }) = embedded_io {
+        let (generics, capacity): (syn::Generics, _) = match capacity {
+            Some(capacity) => (syn::parse_quote! { <R> }, quote::quote! { { #capacity } }),
+            None => (
+                syn::parse_quote! { <R, const N: usize> },
+                quote::quote! { N },
+            ),
+        };
+
+        items.push(syn::Item::Fn(backend_handler(
+            &reject,
+            &handler,
+            &syn::parse_quote! { html_form_actions::embedded_io::EmbeddedIo<R, #capacity> },
+            &generics,
+            &syn::parse_quote! { html_form_actions::embedded_io::Request<R, #capacity> },
+            &actions,
+        )));
+    }
+
+    if let Some(BackendActionAttributes { with, handler }) = backend {
+        items.push(syn::Item::Fn(backend_handler(
+            &reject,
+            &handler,
+            &with,
+            &syn::Generics::default(),
+            &syn::parse_quote! { <#with as html_form_actions::ActionBackend>::Request },
+            &actions,
+        )));
+    }
+
     Ok(syn::ItemMod {
         attrs,
         vis,
@@ -444,31 +2947,204 @@ fn try_actions(
 ///
 /// Function parameters representing form fields should be annotated with the `#[form]` attribute.
 ///
+/// Other parameters are extracted from the request, and so must implement the framework's equivalent of `FromRequestParts`.
+///
+/// The `#[form]` attribute accepts the following options:
+///
+/// - `flatten` - The parameter is a struct deriving [`HtmlForm`](derive@HtmlForm), whose fields are form fields.
+///   Cannot be combined with other options.
+/// - `nested` - The parameter is a struct deriving [`HtmlForm`](derive@HtmlForm), or a `Vec` of such structs,
+///   whose fields are named with the name of the parameter as a prefix, such as `address.street` or `items[3].qty`.
+///   Can only be combined with `rename`.
+/// - `rename` - The name of the form field. Defaults to the name of the parameter.
+/// - `input_type` - The "type" attribute of the HTML input. Defaults to the input type of the parameter's type,
+///   such as "number" for integers, "checkbox" for `bool`, and "text" for `String`.
+/// - `label` - The text of the field's label. Defaults to the name of the parameter as words, e.g. "Unit price" for `unit_price`.
+/// - `placeholder` - The "placeholder" attribute of the HTML input.
+/// - `help` - Text describing the field, to be shown alongside the HTML input.
+/// - `required` - The value must be submitted and not empty.
+/// - `min` and `max` - The minimum and maximum value, as a number or a string, such as a date.
+/// - `step` - The value must be a whole number of steps from `min`.
+/// - `min_length` and `max_length` - The minimum and maximum number of characters.
+/// - `pattern` - A regular expression which the entire value must match, which is checked when the macro is expanded
+///   and compiled once per field. Requires the `pattern` feature, which requires `std`.
+///
+/// Constraints are enforced by the generated handlers after the field has been parsed, and have the same semantics as in HTML.
+///
+/// Each `#[form]` parameter must implement [`FormField`](https://docs.rs/html_form_actions/latest/html_form_actions/trait.FormField.html).
+/// Every field is parsed independently, and a parameter of type `Result<T, FieldError>` receives the error rather than rejecting the submission.
+/// A `bool` parameter is `false` if the field is not submitted, as with an unchecked checkbox,
+/// and an `Option<T>` parameter is `None` if the field is not submitted or is empty.
+/// A `Vec<T>` or `heapless::Vec<T, N>` parameter collects every value submitted for the field, such as from a group of checkboxes,
+/// and a `heapless::String<N>` parameter rejects a value longer than `N` bytes, as having more than `N / 4` characters.
+/// A `&str` or `Cow<str>` parameter borrows the value from the request body, which is decoded in place, so that no allocation is needed.
+/// Their lifetimes may be elided, and the generated `ParsedAction` enum has a lifetime if any action has such a parameter.
+///
+/// If any field is rejected, the request is rejected with "400 Bad Request",
+/// unless the action declares a `fail` handler using `#[action(fail = handler)]`, or the module declares a `reject` handler.
+/// The `fail` handler is called with the action's `Failure`, followed by the non-form parameters of the action.
+/// These parameters are extracted once, and the action is given a clone of each, so they must implement `Clone`.
+/// Actions with a `fail` handler must return an [`ActionResult`](https://docs.rs/html_form_actions/latest/html_form_actions/type.ActionResult.html),
+/// and returning `Err(errors)` also calls the `fail` handler, allowing the page to be re-rendered with error messages and the submitted values.
+///
 /// # Attributes
 ///
-/// - `state` - The "state" used in generated handlers.
+/// - `state` - The "state" used in the handlers generated by the `axum` and `picoserve` integrations.
+///   The other integrations extract the state using the other parameters of the actions, so it is an error to declare it with them.
+/// - `reject` - The handler called with a [`Rejection`](https://docs.rs/html_form_actions/latest/html_form_actions/struct.Rejection.html)
+///   when the submission of an action without a `fail` handler is rejected.
 /// - `axum` - Integrate with [`axum`](https://docs.rs/axum).
 ///   - `handler` - The name of the generated handler to be used as the POST handler. Defaults to `actions_handler`.
+///
+///   Each integration must have a different handler, so if a module declares several integrations whose handlers have the same name by default,
+///   such as `axum` and `http`, all but one must declare a different name.
+/// - `actix_web` - Integrate with [`actix-web`](https://docs.rs/actix-web).
+///   - `handler` - The name of the generated handler to be used as the POST handler. Defaults to `actions_handler`.
+///
+///   The other parameters of the actions are actix extractors, so the state is extracted using `web::Data<State>`.
+/// - `rocket` - Integrate with [`rocket`](https://docs.rs/rocket).
+///   - `handler` - The name of the generated struct which implements [`Handler`](https://docs.rs/rocket/latest/rocket/route/trait.Handler.html).
+///
+///   The other parameters of the actions are request guards, so the state is extracted using `&State<State>`.
+///   The body is read using Rocket's [`Form`](https://docs.rs/rocket/latest/rocket/form/struct.Form.html), so it may be urlencoded or `multipart/form-data`,
+///   and is limited by the `form` and `data-form` limits. The decoded fields are collected into the `#[form]` parameters
+///   by an implementation of [`FromForm`](https://docs.rs/rocket/latest/rocket/form/trait.FromForm.html) for each action.
+/// - `poem` - Integrate with [`poem`](https://docs.rs/poem).
+///   - `handler` - The name of the generated struct which implements [`Endpoint`](https://docs.rs/poem/latest/poem/trait.Endpoint.html).
+///     Defaults to `ActionsEndpoint`.
+///   - `limit` - The maximum length of the body in bytes, such as `poem(limit = 4096)`. Defaults to 2 MiB.
+///
+///   The other parameters of the actions are poem extractors, so the state is extracted using `Data<&State>`.
+/// - `salvo` - Integrate with [`salvo`](https://docs.rs/salvo).
+///   - `handler` - The name of the generated struct which implements [`Handler`](https://docs.rs/salvo/latest/salvo/trait.Handler.html).
+///     Defaults to `ActionsHandler`.
+///
+///   Parameters of type `&Depot` receive the depot of the request, so the state is obtained using `depot.get_typed::<State>()`,
+///   such as when it is injected with `affix_state`. The other parameters of the actions implement `Extractible`.
 /// - `picoserve` - Integrate with [`picoserve`](https://docs.rs/picoserve).
 ///   - `path_parameters` - The types of the path parameters.
 ///   - `handler` - The name of the generated struct which implements [`RequestHandlerService`](https://docs.rs/picoserve/latest/picoserve/routing/trait.RequestHandlerService.html).
+/// - `http` - Integrate with the [`http`](https://docs.rs/http) crate, such as for services built directly on hyper.
+///   Requires the `http` feature of `html_form_actions`.
+///   - `handler` - The name of the generated handler. Defaults to `actions_handler`.
+/// - `embedded_io` - Integrate with any [`embedded_io_async`](https://docs.rs/embedded-io-async) connection, without picoserve or an allocator.
+///   Requires the `embedded_io` feature of `html_form_actions`.
+///   - `handler` - The name of the generated handler. Defaults to `actions_handler`.
+///   - `capacity` - The capacity of the requests, such as `1024`, which is shared by the head and the body,
+///     and allows actions to return a `Response` or `heapless::String`
+///     of that capacity. Otherwise the handler is generic over the capacity.
+/// - `backend` - Integrate with any other framework, using a type which implements
+///   [`ActionBackend`](https://docs.rs/html_form_actions/latest/html_form_actions/trait.ActionBackend.html).
+///   - `with` - The type which implements `ActionBackend`.
+///   - `handler` - The name of the generated handler. Defaults to `dispatch_action`.
 ///
 /// # Macro Output
 ///
 /// The macro modifies the module, inserting the following content:
 ///
-/// - For each "action", a module with the same name is generated, containing:
+/// - For each "action", a module with the same name is generated, such as `my_action`, or `clear` for `r#clear`,
+///   which is also the name used in its query, such as `?/clear`.
+///   The module and the items below are visible to the parent of the module, as is the parsed action enum, containing:
 ///   - A `pub struct` called `Form` representing the form values, with the following field:
 ///     - `action` - The name of the action, to be used as the "action" attribute of the HTML form.
 ///     - For each `#[form]` parameter, `{parameter_name}_name` - The name of the form field, to be used as the "name" attribute of the HTML input.
+///     - For each `#[form]` parameter, `{parameter_name}_id` - The "id" attribute of the HTML input, such as `page-update-value`,
+///       which is namespaced by the module and the action, to be used as the "for" attribute of its label.
+///       Forms which are rendered several times on the same page should append a suffix using [`IdExt::with_suffix`](https://docs.rs/html_form_actions/latest/html_form_actions/trait.IdExt.html).
+///     - For each `#[form]` parameter, `{parameter_name}_error_id` - The "id" attribute of the element containing the field's error message,
+///       to be used as the "aria-describedby" attribute of the HTML input.
+///     - For each `#[form]` parameter, `{parameter_name}_label` - The text of the field's label.
+///     - For each `#[form]` parameter, `{parameter_name}_placeholder` and `{parameter_name}_help` - The placeholder and help text, if any.
+///     - For each `#[form]` parameter, `{parameter_name}_input_type` - The "type" attribute of the HTML input.
+///     - For each `#[form]` parameter, `{parameter_name}_multiple` - Whether the field accepts multiple values,
+///       to be used as the "multiple" attribute of an HTML select.
+///     - For each `#[form]` parameter, `{parameter_name}_options` - The `(value, label)` pair of each option
+///       of a parameter whose type derives `FormOptions`, or an empty slice otherwise.
+///     - For each `#[form]` parameter, `{parameter_name}_step` - The "step" attribute of the HTML input, if any,
+///       which is either declared using `step`, or derived from the parameter's type, such as `"1"` for integers.
+///     - For each `#[form]` parameter, `{parameter_name}_max_length` - The "maxlength" attribute of the HTML input, if any,
+///       which is either declared using `max_length`, or derived from the parameter's type, such as `N / 4` for `heapless::String<N>`.
+///     - For each `#[form(flatten)]` parameter, `{parameter_name}` - The `Form` of the parameter's type.
+///     - For each `#[form(nested)]` parameter, `{parameter_name}_name` - The prefix of the names of the nested fields.
+///     - For each other constraint declared on a `#[form]` parameter, `{parameter_name}_{constraint}` - The value of the constraint,
+///       to be used as the corresponding attribute of the HTML input, e.g. `value_min` as the "min" attribute.
+///   - For each `#[form(flatten)]` or `#[form(nested)]` parameter, a method of `Form` called `{parameter_name}`,
+///     which builds the names and ids of the fields of the parameter's type, which are namespaced by the module and the action,
+///     e.g. `update::FORM.items().at(3).qty_name()` is `items[3].qty`, and `update::FORM.items().at(3).qty_id()` is `page-update-items-3-qty`.
 ///   - A `pub const` called `FORM`, containing the values of `Form`.
+///   - A `pub struct` called `Values`, containing the raw submitted value of each `#[form]` parameter,
+///     which is an `Option<&str>`, or a collection of `&str` for multi-valued parameters.
+///     Its `to_urlencoded` method encodes the values as `application/x-www-form-urlencoded` while formatting.
+///   - A `pub struct` called `Errors`, containing the reason each `#[form]` parameter was rejected, if it was.
+///   - A function called `render`, which renders a default HTML form for the action from the description of its `#[form]` parameters,
+///     with a labelled input for each field, prefilled with the given `Values` and showing the given `Errors`, and a submit button.
+///   - A `pub struct` called `Prefill`, containing the value used to prefill the HTML input of each `#[form]` parameter,
+///     as an `Option<FormValue>`, which is either a raw submitted value or a typed value, and can be created from `&Values`.
+///   - A `pub struct` called `Typed`, with a type parameter for each `#[form]` parameter, containing its typed value,
+///     which may be any type implementing `ToFormField`, such as `&str` for a `String` parameter,
+///     or `ToHtmlForm` and `ToNestedForm` for `#[form(flatten)]` and `#[form(nested)]` parameters, such as `address::Typed`.
+///   - A `pub type` called `Submission`, and a function called `submission`, which creates a submission of the given `Values`,
+///     with the request target, such as `?/my_action`, and the `application/x-www-form-urlencoded` body, to construct submissions programmatically.
+///     A function called `typed_submission` creates a submission of the given `Typed` values.
+///   - A `pub struct` called `Action`, which implements [`FormAction`](https://docs.rs/html_form_actions/latest/html_form_actions/trait.FormAction.html),
+///     for use with code which is generic over actions, such as test clients.
+///   - If the action has a `fail` handler, a `pub type` called `Failure`, which contains the action's `Values` and `Errors`.
+///   - A struct called `Parsed` and a function called `parse`, visible only within the module, which parse a body into the action's
+///     `#[form]` parameters, and are shared by each integration and by `ParsedAction::parse`.
+///     The fields are collected by a struct called `Collected`, which integrations that decode the body themselves push fields to.
+///
+/// - If `parse` is declared, such as `#[actions(parse)]`:
+///   - A `pub(super) enum` called `ParsedAction`, with a variant for each action, such as `MyAction` for `my_action`, containing its parsed `#[form]` parameters,
+///     which has a lifetime if any `#[form]` parameter borrows from the body, such as `&str`.
+///     Its `parse` function parses a body as a submission of the named action, such as `ParsedAction::parse("my_action", &mut body)`,
+///     so that submissions can be dispatched without axum or picoserve.
+///     The body is a `&mut [u8]`, rather than a `&[u8]`, as it is decoded in place, as by each integration,
+///     so that `&str` parameters borrow from it without an allocator. A body which must be kept can be copied first.
+///   - A `pub(super) enum` called `ParseError`, which is returned by `ParsedAction::parse` if the action is not found, if the body is not valid UTF-8,
+///     or with a variant for each action, containing the action's `Values` and `Errors` if the submission is rejected.
+///   - The names of the enums may be declared with `parse(action = MyParsedAction, error = MyParseError)`.
 ///
 /// - If `axum` integration is declared:
 ///   - A function which can be used as an axum [`Handler`](https://docs.rs/axum/latest/axum/handler/index.html), which routes the request to the appropriate `#[action]`.
 ///
+/// - If `actix_web` integration is declared:
+///   - A function which can be used as an actix-web [`Handler`](https://docs.rs/actix-web/latest/actix_web/trait.Handler.html), which routes the request to the appropriate `#[action]`.
+///
+/// - If `rocket` integration is declared:
+///   - A struct which implements [`Handler`](https://docs.rs/rocket/latest/rocket/route/trait.Handler.html) by routing the request to the appropriate `#[action]`,
+///     and can be mounted at the path of the page, such as `rocket.mount("/page", ActionsHandler)`.
+///     The other parameters of the action are request guards, which are run before the body is read,
+///     so that a guard which forwards the request, such as to a login route, forwards it with its body.
+///   - An implementation of [`FromForm`](https://docs.rs/rocket/latest/rocket/form/trait.FromForm.html) for the `Collected` struct of each action.
+///
+/// - If `poem` integration is declared:
+///   - A struct which implements [`Endpoint`](https://docs.rs/poem/latest/poem/trait.Endpoint.html) by routing the request to the appropriate `#[action]`,
+///     such as `Route::new().at("/page", post(ActionsEndpoint).data(state))`.
+///     Bodies longer than the limit, which may be declared in bytes with `poem(limit = 4096)`, are rejected with "413 Payload Too Large".
+///
+/// - If `salvo` integration is declared:
+///   - A struct which implements [`Handler`](https://docs.rs/salvo/latest/salvo/trait.Handler.html) by routing the request to the appropriate `#[action]`,
+///     such as `Router::with_path("page").post(ActionsHandler)`.
+///
 /// - If `picoserve` integration is declared:
 ///   - A struct which implements [`RequestHandlerService`](https://docs.rs/picoserve/latest/picoserve/routing/trait.RequestHandlerService.html) by routing the request to the appropriate `#[action]`.
 ///
+/// - If `http` integration is declared:
+///   - An `async` function which takes an `http::Request<B>`, where `B` is an `Unpin` [`http_body::Body`](https://docs.rs/http-body/latest/http_body/trait.Body.html),
+///     and returns an `http::Response<String>`, by routing the request to the appropriate `#[action]`.
+///     See [`Http`](https://docs.rs/html_form_actions/latest/html_form_actions/http/struct.Http.html) for the supported parameters and return types.
+///     Bodies longer than the limit, which may be declared in bytes with `http(limit = 4096)`, are rejected with "413 Payload Too Large".
+///
+/// - If `embedded_io` integration is declared:
+///   - An `async` function which takes an `embedded_io::Request<R, N>`, which has been read from a connection into buffers of `N` bytes,
+///     and returns an `embedded_io::Response<N>`, by routing the request to the appropriate `#[action]`.
+///     See [`embedded_io`](https://docs.rs/html_form_actions/latest/html_form_actions/embedded_io/index.html) for the supported parameters and return types.
+///
+/// - If `backend` integration is declared:
+///   - An `async` function which takes the backend's `Request` and returns its `Response`, by routing the request to the appropriate `#[action]`.
+///     The other parameters of the actions must implement [`FromActionRequest`](https://docs.rs/html_form_actions/latest/html_form_actions/trait.FromActionRequest.html),
+///     and the values returned by the actions and handlers must implement [`IntoActionResponse`](https://docs.rs/html_form_actions/latest/html_form_actions/trait.IntoActionResponse.html).
+///
 /// All other content is unchanged, allowing you to mix action handlers with other items.
 #[proc_macro_attribute]
 pub fn actions(
@@ -478,3 +3154,360 @@ pub fn actions(
     try_actions(attribute_tokens, tokens)
         .map_or_else(|error| error.into_compile_error().into(), From::from)
 }
+
+/// Convert a `CamelCase` struct name into a `snake_case` module name.
+fn snake_case(ident: &syn::Ident) -> syn::Ident {
+    let name = ident.to_string();
+    let characters = name.chars().collect::<Vec<_>>();
+
+    let mut snake_case = String::new();
+
+    for (index, &character) in characters.iter().enumerate() {
+        if character.is_uppercase() && index > 0 {
+            let previous = characters[index - 1];
+            let next = characters.get(index + 1);
+
+            if previous.is_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_uppercase() && next.is_some_and(|next| next.is_lowercase()))
+            {
+                snake_case.push('_');
+            }
+        }
+
+        snake_case.extend(character.to_lowercase());
+    }
+
+    syn::Ident::new(&snake_case, ident.span())
+}
+
+/// Convert a `snake_case` identifier into `PascalCase`, such as `UnitPrice` for `unit_price`.
+fn pascal_case(ident: &syn::Ident) -> syn::Ident {
+    let name = ident.unraw().to_string();
+
+    let pascal_case = name
+        .split('_')
+        .flat_map(|word| {
+            let mut characters = word.chars();
+
+            characters
+                .next()
+                .into_iter()
+                .flat_map(char::to_uppercase)
+                .chain(characters)
+        })
+        .collect::<String>();
+
+    syn::Ident::new(&pascal_case, ident.span())
+}
+
+/// Convert a `snake_case` name into words, with the first letter in uppercase, such as "Unit price" for `unit_price`.
+fn sentence_case(snake_case: &str) -> String {
+    let mut characters = snake_case.chars();
+
+    characters
+        .next()
+        .into_iter()
+        .flat_map(char::to_uppercase)
+        .chain(characters.map(|character| if character == '_' { ' ' } else { character }))
+        .collect()
+}
+
+fn try_derive_html_form(tokens: proc_macro::TokenStream) -> syn::Result<proc_macro::TokenStream> {
+    let syn::DeriveInput {
+        vis,
+        ident,
+        generics,
+        data,
+        ..
+    } = syn::parse(tokens)?;
+
+    if !generics.params.is_empty() {
+        return Err(syn::Error::new(
+            generics.span(),
+            "HtmlForm cannot be derived for generic structs",
+        ));
+    }
+
+    let syn::Data::Struct(syn::DataStruct {
+        fields: syn::Fields::Named(syn::FieldsNamed { named: fields, .. }),
+        ..
+    }) = data
+    else {
+        return Err(syn::Error::new(
+            ident.span(),
+            "HtmlForm can only be derived for structs with named fields",
+        ));
+    };
+
+    let inputs = fields
+        .into_iter()
+        .map(|mut field| {
+            let form_attrs = maybe_extract_attributes(&mut field.attrs)?.unwrap_or_default();
+
+            FormInput::new(
+                field.ident.expect("named fields have an ident"),
+                field.ty,
+                form_attrs,
+            )
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let module = snake_case(&ident);
+
+    let form_items = form_items(&quote::quote! { pub }, None, None, &inputs);
+
+    let form_const = quote::quote! { #module::FORM };
+
+    let collect_values = inputs.iter().map(|input| input.collect_value(&form_const));
+
+    let parse_inputs = parse_inputs(&inputs);
+
+    let field_idents = inputs.iter().map(FormInput::ident).collect::<Vec<_>>();
+
+    let field_count = rejected_field_count(&inputs);
+
+    let rejected_fields = rejected_fields(&form_const, &inputs);
+
+    let names_methods = inputs.iter().map(FormInput::names_method);
+
+    let first_errors = inputs.iter().map(FormInput::first_error);
+
+    Ok(quote::quote! {
+        #vis mod #module {
+            #[allow(unused_imports)]
+            use super::*;
+
+            #form_items
+
+            /// Builds the names of the form fields, with a prefix if the form is nested.
+            #[derive(Debug, Clone, Copy)]
+            pub struct Names<'p> {
+                pub prefix: html_form_actions::NamePrefix<'p>,
+            }
+
+            impl<'p> Names<'p> {
+                #(#names_methods)*
+            }
+        }
+
+        impl<'r> html_form_actions::HtmlForm<'r> for #ident {
+            type Form = #module::Form;
+
+            const FORM: Self::Form = #module::FORM;
+
+            const FIELD_COUNT: usize = #field_count;
+
+            type Values = #module::Values<'r>;
+
+            type Errors = #module::Errors;
+
+            type Names<'p> = #module::Names<'p>;
+
+            type Prefill<'a> = #module::Prefill<'a>;
+
+            fn names(prefix: html_form_actions::NamePrefix<'_>) -> Self::Names<'_> {
+                #module::Names { prefix }
+            }
+
+            #[allow(unused_variables)]
+            fn push_value(values: &mut Self::Values, errors: &mut Self::Errors, name: &str, value: &'r str) {
+                #(#collect_values)*
+            }
+
+            #[allow(unused_variables)]
+            fn from_form(values: &Self::Values, errors: &mut Self::Errors) -> Option<Self> {
+                #parse_inputs
+
+                #[allow(irrefutable_let_patterns)]
+                let (#(Some(#field_idents),)*) = (#(#field_idents,)*) else {
+                    return None;
+                };
+
+                Some(Self { #(#field_idents,)* })
+            }
+
+            #[allow(unused_variables)]
+            fn first_error(errors: &Self::Errors) -> Option<html_form_actions::FieldError> {
+                None #(.or(#first_errors))*
+            }
+
+            #[allow(unused_variables)]
+            fn prefill(values: &Self::Values) -> Self::Prefill<'_> {
+                values.into()
+            }
+
+            fn visit_pairs(
+                prefix: html_form_actions::NamePrefix<'_>,
+                values: &Self::Values,
+                visit: &mut dyn FnMut(html_form_actions::FieldName<'_>, html_form_actions::FormValue<'_>) -> core::ops::ControlFlow<()>,
+            ) -> core::ops::ControlFlow<()> {
+                #module::visit_pairs(prefix, values, visit)
+            }
+
+            fn render_fields(
+                f: &mut core::fmt::Formatter<'_>,
+                prefix: html_form_actions::NamePrefix<'_>,
+                values: &Self::Values,
+                errors: &Self::Errors,
+            ) -> core::fmt::Result {
+                #module::render_fields(f, prefix, values, errors)
+            }
+
+            fn rejected_fields<'a>(
+                values: &'a Self::Values,
+                errors: &'a Self::Errors,
+            ) -> impl Iterator<Item = html_form_actions::RejectedField<'a>> {
+                #rejected_fields
+            }
+        }
+    }
+    .into())
+}
+
+/// Derive `HtmlForm` for a struct, allowing it to be used as a `#[form(flatten)]` parameter of actions.
+///
+/// Each field of the struct is a form field, and accepts the same `#[form(...)]` options as the `#[form]` parameters of actions,
+/// including `#[form(flatten)]`.
+///
+/// The macro generates a module named after the struct in `snake_case`, with the same visibility as the struct, containing:
+///
+/// - A `pub struct` called `Form`, with the same fields as an action's `Form`, except for `action`.
+///   The ids of the fields are the ids within the struct, such as `street`, as the ids are namespaced by the action which uses the struct,
+///   and are built by the `Names` of the action's flattened or nested parameter, such as `update::FORM.address().street_id()`.
+/// - A `pub const` called `FORM`, containing the values of `Form`.
+/// - A `pub struct` called `Values`, containing the raw submitted value of each field.
+/// - A `pub struct` called `Errors`, containing the reason each field was rejected, if it was.
+/// - A `pub struct` called `Prefill`, containing the value used to prefill the HTML input of each field.
+/// - A `pub struct` called `Names`, which builds the names of the fields when the struct is nested,
+///   with methods called `{field_name}_name`, `{field_name}_id` and `{field_name}_error_id` for each field,
+///   and a method with the same name as each flattened or nested field.
+///
+/// When the struct is used as a `#[form(flatten)]` parameter, the action's `Form`, `Values` and `Errors` contain these types
+/// in a field with the same name as the parameter, and the names of the fields are not prefixed.
+#[proc_macro_derive(HtmlForm, attributes(form))]
+pub fn derive_html_form(tokens: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    try_derive_html_form(tokens).unwrap_or_else(|error| error.into_compile_error().into())
+}
+
+fn try_derive_form_options(
+    tokens: proc_macro::TokenStream,
+) -> syn::Result<proc_macro::TokenStream> {
+    #[derive(deluxe::ExtractAttributes)]
+    #[deluxe(attributes(form))]
+    struct OptionAttrs {
+        #[deluxe(default)]
+        value: Option<String>,
+        #[deluxe(default)]
+        label: Option<String>,
+    }
+
+    let syn::DeriveInput {
+        ident,
+        generics,
+        data,
+        ..
+    } = syn::parse(tokens)?;
+
+    if !generics.params.is_empty() {
+        return Err(syn::Error::new(
+            generics.span(),
+            "FormOptions cannot be derived for generic enums",
+        ));
+    }
+
+    let syn::Data::Enum(syn::DataEnum { variants, .. }) = data else {
+        return Err(syn::Error::new(
+            ident.span(),
+            "FormOptions can only be derived for enums",
+        ));
+    };
+
+    let mut variant_idents = Vec::new();
+    let mut values = Vec::new();
+    let mut labels = Vec::new();
+
+    for mut variant in variants {
+        if !matches!(variant.fields, syn::Fields::Unit) {
+            return Err(syn::Error::new(
+                variant.fields.span(),
+                "FormOptions variants cannot have fields",
+            ));
+        }
+
+        let (value, label) = match maybe_extract_attributes(&mut variant.attrs)? {
+            Some(OptionAttrs { value, label }) => (value, label),
+            None => (None, None),
+        };
+
+        let snake_case = snake_case(&variant.ident).to_string();
+
+        let value = value.unwrap_or_else(|| snake_case.clone());
+
+        // "InProgress" is labelled "In progress"
+        let label = label.unwrap_or_else(|| sentence_case(&snake_case));
+
+        if values.contains(&value) {
+            return Err(syn::Error::new(
+                variant.ident.span(),
+                format!("the value {value:?} is used by more than one variant"),
+            ));
+        }
+
+        variant_idents.push(variant.ident);
+        values.push(value);
+        labels.push(label);
+    }
+
+    Ok(quote::quote! {
+        impl html_form_actions::FormOptions for #ident {
+            const OPTIONS: &'static [(&'static str, &'static str)] = &[#((#values, #labels),)*];
+
+            fn value(&self) -> &'static str {
+                match self {
+                    #(Self::#variant_idents => #values,)*
+                }
+            }
+
+            fn label(&self) -> &'static str {
+                match self {
+                    #(Self::#variant_idents => #labels,)*
+                }
+            }
+        }
+
+        impl html_form_actions::FromFormValue<'_> for #ident {
+            const INPUT_TYPE: &'static str = "radio";
+            const OPTIONS: &'static [(&'static str, &'static str)] = <Self as html_form_actions::FormOptions>::OPTIONS;
+
+            fn from_form_value(value: &str) -> Result<Self, html_form_actions::FieldError> {
+                match value {
+                    #(#values => Ok(Self::#variant_idents),)*
+                    _ => Err(html_form_actions::FieldError::Invalid),
+                }
+            }
+        }
+
+        impl html_form_actions::ToFormValue for #ident {
+            fn fmt_form_value(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.write_str(html_form_actions::FormOptions::value(self))
+            }
+        }
+    }
+    .into())
+}
+
+/// Derive `FormOptions` for a fieldless enum, allowing it to be used as a `#[form]` parameter
+/// rendered as a `<select>` or a group of radio inputs.
+///
+/// Each variant accepts the following `#[form(...)]` options:
+///
+/// - `value` - The submitted value. Defaults to the name of the variant in `snake_case`.
+/// - `label` - The label shown to the user. Defaults to the name of the variant as words, e.g. "In progress" for `InProgress`.
+///
+/// The action's `Form` contains `{parameter_name}_options`, a slice of the `(value, label)` pair of each variant,
+/// and the parameter accepts exactly those values.
+#[proc_macro_derive(FormOptions, attributes(form))]
+pub fn derive_form_options(tokens: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    try_derive_form_options(tokens).unwrap_or_else(|error| error.into_compile_error().into())
+}